@@ -0,0 +1,389 @@
+//! Formal concept analysis.
+//!
+//! A formal context is a heterogeneous [`Relation`] `I` between a set of
+//! objects `G` and a set of attributes `M`, where `gIm` means that the
+//! object `g` has the attribute `m`. The derivation operators of the context
+//! are the strict image and pre-image of `I`:
+//!
+//! - `A' = { m ∈ M: ∀g ∈ A: gIm }` for `A ⊆ G`, see [`Relation::image_strict`]
+//! - `B' = { g ∈ G: ∀m ∈ B: gIm }` for `B ⊆ M`, see [`Relation::preimage_strict`]
+//!
+//! A formal concept is a pair `(A, B)` with `A' = B` and `B' = A`.
+//! An attribute implication `B ⇒ C` holds in the context iff `C ⊆ B''`.
+
+use std::fmt;
+use std::vec::Vec;
+
+use crate::set::{Set, SetElement};
+use crate::relation::{Relation, RelationBits, RelationVec};
+use crate::relation::relation_bits::{ones, contains, WORD_BITS};
+
+/// A formal concept `(A, B)` of a context, where the extent `A` is the set
+/// of all objects sharing the attributes of the intent `B` and vice versa.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Concept {
+	pub extent: Set,
+	pub intent: Set,
+}
+
+/// Enumerate all formal concepts of the `context`.
+///
+/// The concepts are calculated with Ganter's NextClosure algorithm and are
+/// returned in the lectic order of their intents, where the attributes are
+/// ordered like the domain.
+pub fn concepts<R: Relation>(context: &R) -> Vec<Concept> {
+	let ctx = Context::new(context);
+	let mut concepts = Vec::new();
+	let mut intent = Some(ctx.closure(&ctx.empty()));
+	while let Some(b) = intent {
+		concepts.push(Concept {
+			extent: ctx.objects(&ctx.extent(&b)),
+			intent: ctx.attributes(&b),
+		});
+		intent = next_closure(&b, ctx.m, |b| ctx.closure(b));
+	}
+	concepts
+}
+
+/// The concept lattice of the `context`, i.e. its concepts ordered by the
+/// inclusion of their extents.
+///
+/// Every concept is represented by its extent as a [`SetElement::Set`],
+/// the intent of an extent `A` is `A'`, see [`Relation::image_strict`].
+pub fn concept_lattice<R: Relation>(context: &R) -> RelationVec {
+	let extents: Set = concepts(context).into_iter()
+		.map(|c| SetElement::from(c.extent))
+		.collect();
+	let sets: Vec<&Set> = extents.iter()
+		.map(|e| match e {
+			SetElement::Set(s) => s,
+			_ => unreachable!(),
+		})
+		.collect();
+	let table = sets.iter()
+		.flat_map(|a| sets.iter().map(move |b| a.is_subset(b)))
+		.collect();
+	RelationVec::new((extents.clone(), extents), table)
+}
+
+/// An attribute implication `premise ⇒ conclusion`, stating that every
+/// object having all attributes of the premise also has all attributes of
+/// the conclusion.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Implication {
+	pub premise: Set,
+	pub conclusion: Set,
+}
+
+impl Implication {
+	/// Return `true` if the implication holds in the `context`,
+	/// i.e. if `conclusion ⊆ premise''`.
+	pub fn holds<R: Relation>(&self, context: &R) -> bool {
+		let objects = context.preimage_strict(&self.premise);
+		let attributes = context.image_strict(&objects);
+		self.conclusion.is_subset(&attributes)
+	}
+}
+
+impl fmt::Display for Implication {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} ⇒ {}", self.premise, self.conclusion)
+	}
+}
+
+/// The canonical implication basis (Duquenne–Guigues basis) of the `context`.
+///
+/// Every implication holding in the context follows from the basis, and no
+/// basis with fewer implications exists. The premises are the pseudo-intents
+/// of the context in lectic order, every conclusion is `premise'' \ premise`.
+pub fn canonical_basis<R: Relation>(context: &R) -> Vec<Implication> {
+	let ctx = Context::new(context);
+	let mut basis: Vec<(Vec<u64>, Vec<u64>)> = Vec::new();
+	let mut a = Some(ctx.empty());
+	while let Some(premise) = a {
+		let closure = ctx.closure(&premise);
+		if closure != premise {
+			let conclusion: Vec<u64> = closure.iter().zip(premise.iter()).map(|(c, p)| c & !p).collect();
+			basis.push((premise.clone(), conclusion));
+		}
+		// the next set closed under the implications with smaller premises
+		a = next_closure(&premise, ctx.m, |b| pseudo_closure(&basis, b));
+	}
+	basis.iter()
+		.map(|(p, c)| Implication { premise: ctx.attributes(p), conclusion: ctx.attributes(c) })
+		.collect()
+}
+
+/// The smallest superset of `set` closed under the `implications`,
+/// i.e. containing the conclusion of every implication whose premise it contains.
+pub fn implication_closure(implications: &[Implication], set: &Set) -> Set {
+	let mut closure = set.clone();
+	loop {
+		let added: Vec<SetElement> = implications.iter()
+			.filter(|i| i.premise.is_subset(&closure) && !i.conclusion.is_subset(&closure))
+			.flat_map(|i| i.conclusion.iter())
+			.cloned()
+			.collect();
+		if added.is_empty() {
+			return closure;
+		}
+		closure = closure.iter().cloned().chain(added).collect();
+	}
+}
+
+/// The smallest superset of `b` containing the conclusion of every
+/// implication whose premise is a proper subset (`L*` closure).
+fn pseudo_closure(implications: &[(Vec<u64>, Vec<u64>)], b: &[u64]) -> Vec<u64> {
+	let mut closure = b.to_vec();
+	let mut changed = true;
+	while changed {
+		changed = false;
+		for (premise, conclusion) in implications.iter() {
+			if is_subset(premise, &closure) && premise != &closure && !is_subset(conclusion, &closure) {
+				for (c, &w) in closure.iter_mut().zip(conclusion) {
+					*c |= w;
+				}
+				changed = true;
+			}
+		}
+	}
+	closure
+}
+
+/// A formal context with the attributes of every object as a bit set.
+struct Context<'a> {
+	objects: Vec<&'a SetElement>,
+	attributes: Vec<&'a SetElement>,
+	bits: RelationBits,
+	/// Number of attributes.
+	m: usize,
+}
+
+impl<'a> Context<'a> {
+	fn new<R: Relation>(r: &'a R) -> Self {
+		Context {
+			objects: r.get_domain().0.iter().collect(),
+			attributes: r.get_domain().1.iter().collect(),
+			bits: RelationBits::from_relation(r),
+			m: r.get_domain().1.cardinality(),
+		}
+	}
+	/// The empty set of attributes.
+	fn empty(&self) -> Vec<u64> {
+		vec![0; self.m.div_ceil(WORD_BITS)]
+	}
+	/// The objects having all attributes of `b`, i.e. `B'`.
+	fn extent(&self, b: &[u64]) -> Vec<usize> {
+		(0..self.objects.len())
+			.filter(|&g| is_subset(b, self.bits.row(g)))
+			.collect()
+	}
+	/// The attributes shared by all objects having the attributes of `b`, i.e. `B''`.
+	fn closure(&self, b: &[u64]) -> Vec<u64> {
+		let mut closure = self.empty();
+		for i in 0..self.m {
+			insert(&mut closure, i);
+		}
+		for g in self.extent(b) {
+			for (c, &w) in closure.iter_mut().zip(self.bits.row(g)) {
+				*c &= w;
+			}
+		}
+		closure
+	}
+	fn objects(&self, a: &[usize]) -> Set {
+		a.iter().map(|&g| self.objects[g]).cloned().collect()
+	}
+	fn attributes(&self, b: &[u64]) -> Set {
+		ones(b).map(|i| self.attributes[i]).cloned().collect()
+	}
+}
+
+/// The lectically next set closed under `closure` after the closed set `a`
+/// over `m` elements, or `None` if `a` is the last one (NextClosure).
+fn next_closure<F>(a: &[u64], m: usize, closure: F) -> Option<Vec<u64>>
+where F: Fn(&[u64]) -> Vec<u64>,
+{
+	let mut prefix = a.to_vec();
+	for i in (0..m).rev() {
+		if contains(&prefix, i) {
+			remove(&mut prefix, i);
+			continue;
+		}
+		// prefix = a ∩ {0, ..., i-1}
+		let mut b = prefix.clone();
+		insert(&mut b, i);
+		let b = closure(&b);
+		if (0..i).all(|j| contains(&b, j) == contains(&prefix, j)) {
+			return Some(b);
+		}
+	}
+	None
+}
+
+fn insert(b: &mut [u64], i: usize) {
+	b[i / WORD_BITS] |= 1 << (i % WORD_BITS);
+}
+fn remove(b: &mut [u64], i: usize) {
+	b[i / WORD_BITS] &= !(1 << (i % WORD_BITS));
+}
+fn is_subset(a: &[u64], b: &[u64]) -> bool {
+	a.iter().zip(b).all(|(a, b)| a & !b == 0)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::relation::Endorelation;
+	use crate::relation::relation::tests::words;
+	use proptest::{prelude::*, collection::vec};
+
+	/// The numbers 1 to 9 with the attributes
+	/// even, odd, prime, square and composite.
+	fn numbers() -> RelationVec {
+		let attributes = ["composite", "even", "odd", "prime", "square"];
+		let has = |n: i32, a: &str| match a {
+			"composite" => [4, 6, 8, 9].contains(&n),
+			"even" => n % 2 == 0,
+			"odd" => n % 2 == 1,
+			"prime" => [2, 3, 5, 7].contains(&n),
+			"square" => [1, 4, 9].contains(&n),
+			_ => unreachable!(),
+		};
+		let table = (1..10)
+			.flat_map(|n| attributes.iter().map(move |a| has(n, a)))
+			.collect();
+		RelationVec::new(
+			((1..10).collect(), attributes.iter().map(|a| a.to_string()).collect()),
+			table,
+		)
+	}
+	#[test]
+	fn numbers_concepts() {
+		let r = numbers();
+		let concepts = concepts(&r);
+		let intents: Vec<Set> = concepts.iter().map(|c| c.intent.clone()).collect();
+		assert_eq!(intents, vec![
+			words(""),
+			words("square"),
+			words("prime"),
+			words("odd"),
+			words("odd square"),
+			words("odd prime"),
+			words("even"),
+			words("even prime"),
+			words("composite"),
+			words("composite square"),
+			words("composite odd square"),
+			words("composite even"),
+			words("composite even square"),
+			words("composite even odd prime square"),
+		]);
+		let odd_primes = concepts.iter().find(|c| c.intent == words("odd prime")).unwrap();
+		assert_eq!(odd_primes.extent, vec![3, 5, 7].into_iter().collect());
+		for c in concepts.iter() {
+			assert_eq!(r.image_strict(&c.extent), c.intent);
+			assert_eq!(r.preimage_strict(&c.intent), c.extent);
+		}
+	}
+	#[test]
+	fn numbers_lattice() {
+		let lattice = concept_lattice(&numbers());
+		assert_eq!(lattice.get_domain().0.cardinality(), 14);
+		assert!(lattice.is_lattice());
+		let l = lattice.lattice().unwrap();
+		assert_eq!(l.top(), &SetElement::from((1..10).collect::<Set>()));
+		assert_eq!(l.bottom(), &SetElement::from(Set::new()));
+	}
+	#[test]
+	fn empty_context() {
+		let r = RelationVec::new((Set::new(), words("a b")), vec![]);
+		let concepts = concepts(&r);
+		assert_eq!(concepts, vec![Concept { extent: Set::new(), intent: words("a b") }]);
+		let r = RelationVec::new((words("a b"), Set::new()), vec![]);
+		assert_eq!(super::concepts(&r).len(), 1);
+	}
+
+	#[test]
+	fn numbers_basis() {
+		let r = numbers();
+		let basis = canonical_basis(&r);
+		let implication = |p: &str, c: &str| Implication { premise: words(p), conclusion: words(c) };
+		assert_eq!(basis, vec![
+			implication("prime square", "composite even odd"),
+			implication("even square", "composite"),
+			implication("even odd", "composite prime square"),
+			implication("composite prime", "even odd square"),
+			implication("composite odd", "square"),
+		]);
+		assert_eq!(basis[1].to_string(), "{even square} ⇒ {composite}");
+		assert!(basis.iter().all(|i| i.holds(&r)));
+		assert!(!implication("even", "prime").holds(&r));
+		assert_eq!(
+			implication_closure(&basis, &words("odd square even")),
+			words("composite even odd prime square"),
+		);
+		assert_eq!(implication_closure(&basis, &words("odd square")), words("odd square"));
+	}
+
+	/// Contexts with up to 8 objects and attributes.
+	fn context() -> impl Strategy<Value = RelationVec> {
+		(0..8usize, 0..8usize)
+			.prop_flat_map(|(g, m)| (Just((g, m)), vec(any::<bool>(), g * m)))
+			.prop_map(|((g, m), table)| RelationVec::new(
+				((0..g as i32).collect(), (0..m).map(|i| ((b'a' + i as u8) as char).to_string()).collect()),
+				table,
+			))
+	}
+
+	proptest! {
+		#[test]
+		fn all_concepts(r in context()) {
+			let concepts = concepts(&r);
+			let intents: Vec<&Set> = concepts.iter().map(|c| &c.intent).collect();
+			for c in concepts.iter() {
+				assert_eq!(r.image_strict(&c.extent), c.intent);
+				assert_eq!(r.preimage_strict(&c.intent), c.extent);
+			}
+			// the intents are exactly the closure system generated by the object intents
+			assert!(intents.contains(&r.get_domain().1));
+			for g in r.get_domain().0.iter() {
+				assert!(intents.contains(&&r.image_strict(&std::iter::once(g.clone()).collect())));
+			}
+			for a in intents.iter() {
+				for b in intents.iter() {
+					let meet: Set = a.intersection(b).cloned().collect();
+					assert!(intents.contains(&&meet));
+				}
+			}
+			// every intent is enumerated once
+			let mut sorted = intents.clone();
+			sorted.sort();
+			sorted.dedup();
+			assert_eq!(sorted.len(), intents.len());
+
+			assert!(concept_lattice(&r).is_lattice());
+		}
+		#[test]
+		fn implication_basis(r in context()) {
+			let basis = canonical_basis(&r);
+			assert!(basis.iter().all(|i| i.holds(&r)));
+			// the basis is complete: the closure of every set of attributes is B''
+			let attributes: Vec<&SetElement> = r.get_domain().1.iter().collect();
+			for bits in 0..1usize << attributes.len() {
+				let b: Set = (0..attributes.len())
+					.filter(|i| bits & (1 << i) != 0)
+					.map(|i| attributes[i].clone())
+					.collect();
+				assert_eq!(implication_closure(&basis, &b), r.image_strict(&r.preimage_strict(&b)));
+			}
+			// and non-redundant
+			for (i, implication) in basis.iter().enumerate() {
+				let mut rest = basis.clone();
+				rest.remove(i);
+				let closure = implication_closure(&rest, &implication.premise);
+				assert!(!implication.conclusion.is_subset(&closure));
+			}
+		}
+	}
+}
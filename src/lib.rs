@@ -2,12 +2,18 @@
 
 pub mod set;
 pub mod relation;
+pub mod fca;
 pub mod tex;
 pub mod random;
 mod cow;
+mod union_find;
 
 pub use crate::relation::Relation;
 pub use crate::relation::Endorelation;
+pub use crate::relation::RelationMut;
 pub use crate::relation::RelationVec;
+pub use crate::relation::RelationBits;
+pub use crate::relation::RelationSparse;
+pub use crate::relation::RelationBdd;
 pub use crate::set::Set;
 pub use crate::set::SetElement;
@@ -1,9 +1,22 @@
 pub mod relation;
 pub mod endorelation;
+pub mod relation_mut;
+pub mod product;
+pub mod powerset;
+pub mod lattice;
+pub mod linear_extension;
+mod dilworth;
 pub mod relation_vec;
+pub mod relation_bits;
+pub mod relation_sparse;
+pub mod relation_bdd;
 
 pub use crate::relation::relation::Relation;
 pub use crate::relation::endorelation::Endorelation;
+pub use crate::relation::relation_mut::RelationMut;
 pub use crate::relation::relation_vec::RelationVec;
+pub use crate::relation::relation_bits::RelationBits;
+pub use crate::relation::relation_sparse::RelationSparse;
+pub use crate::relation::relation_bdd::RelationBdd;
 
-pub use crate::relation::relation::eq;
+pub use crate::relation::relation::{eq, is_subrelation, is_strict_subrelation, is_disjoint, compare};
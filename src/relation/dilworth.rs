@@ -0,0 +1,213 @@
+//! Chains and antichains of the partial order generated by an acyclic relation.
+//!
+//! By Dilworth's theorem, the size of a largest antichain equals the
+//! smallest number of chains covering the order. Both are calculated from a
+//! maximum matching between two copies of the domain, connected by the
+//! strict order `<`: every matched pair `x < y` links `x` and `y` in a chain.
+
+use std::vec::Vec;
+
+use crate::set::{Set, SetElement};
+use crate::relation::{Relation, Endorelation, RelationBits, RelationMut};
+use crate::relation::relation_bits::ones;
+use crate::relation::linear_extension::{Cycle, topological_sort, cycle};
+
+/// The strict order `<` generated by `r` and a linear extension of it.
+fn strict_order<R: Endorelation>(r: &R) -> Result<(RelationBits, Vec<usize>), Cycle> {
+	let order = topological_sort(r).map_err(|c| cycle(r, c))?;
+	let mut strict = R::closure_transitive(r);
+	for i in r.ixs() {
+		strict.set_at(i, i, false);
+	}
+	Ok((strict, order))
+}
+
+fn elements<'a, R: Endorelation, I>(r: &R, indices: I) -> Set
+where I: IntoIterator<Item = &'a usize>,
+{
+	let elements: Vec<&SetElement> = r.get_domain().0.iter().collect();
+	indices.into_iter().map(|&i| elements[i]).cloned().collect()
+}
+
+/// A longest chain of the order generated by `r`.
+pub(crate) fn longest_chain<R: Endorelation>(r: &R) -> Result<Set, Cycle> {
+	let (strict, order) = strict_order(r)?;
+	// the longest chain ending in every element and its predecessor therein
+	let mut length = vec![0; order.len()];
+	let mut previous = vec![None; order.len()];
+	for &iy in order.iter() {
+		let best = order.iter()
+			.take_while(|&&ix| ix != iy)
+			.filter(|&&ix| strict.eval_at(ix, iy))
+			.max_by_key(|&&ix| length[ix]);
+		length[iy] = 1 + best.map_or(0, |&ix| length[ix]);
+		previous[iy] = best.cloned();
+	}
+	let mut chain = Vec::new();
+	let mut last = (0..order.len()).max_by_key(|&i| length[i]);
+	while let Some(i) = last {
+		chain.push(i);
+		last = previous[i];
+	}
+	Ok(elements(r, chain.iter()))
+}
+
+/// A maximum matching of `x` to `y` with `x < y`, given as the
+/// matched partner of every `y`.
+fn matching(strict: &RelationBits) -> Vec<Option<usize>> {
+	let n = strict.get_domain().0.cardinality();
+	let mut partner = vec![None; n];
+	for ix in 0..n {
+		augment(strict, ix, &mut vec![false; n], &mut partner);
+	}
+	partner
+}
+
+/// Search an augmenting path from the unmatched `ix` (Kuhn's algorithm).
+fn augment(strict: &RelationBits, ix: usize, visited: &mut [bool], partner: &mut [Option<usize>]) -> bool {
+	for iy in ones(strict.row(ix)) {
+		if visited[iy] { continue; }
+		visited[iy] = true;
+		let free = match partner[iy] {
+			None => true,
+			Some(iz) => augment(strict, iz, visited, partner),
+		};
+		if free {
+			partner[iy] = Some(ix);
+			return true;
+		}
+	}
+	false
+}
+
+/// A smallest partition of the order generated by `r` into chains.
+pub(crate) fn chain_decomposition<R: Endorelation>(r: &R) -> Result<Vec<Set>, Cycle> {
+	let (strict, _) = strict_order(r)?;
+	let partner = matching(&strict);
+	let mut next = vec![None; partner.len()];
+	for (iy, ix) in partner.iter().enumerate() {
+		if let Some(ix) = *ix {
+			next[ix] = Some(iy);
+		}
+	}
+	// every chain starts at an element without a matched predecessor
+	let chains = (0..partner.len())
+		.filter(|&i| partner[i].is_none())
+		.map(|start| {
+			let mut chain = vec![start];
+			while let Some(i) = next[*chain.last().unwrap()] {
+				chain.push(i);
+			}
+			elements(r, chain.iter())
+		})
+		.collect();
+	Ok(chains)
+}
+
+/// A largest antichain of the order generated by `r`.
+///
+/// Derived from a minimum vertex cover of the matching graph (König's theorem):
+/// the elements with neither copy in the cover are pairwise incomparable.
+pub(crate) fn largest_antichain<R: Endorelation>(r: &R) -> Result<Set, Cycle> {
+	let (strict, _) = strict_order(r)?;
+	let partner = matching(&strict);
+	let n = partner.len();
+	let mut matched = vec![false; n];
+	for &ix in partner.iter().flatten() {
+		matched[ix] = true;
+	}
+	// alternating paths from the unmatched left copies
+	let (mut left, mut right) = (vec![false; n], vec![false; n]);
+	let mut stack: Vec<usize> = (0..n).filter(|&i| !matched[i]).collect();
+	for &i in stack.iter() {
+		left[i] = true;
+	}
+	while let Some(ix) = stack.pop() {
+		for iy in ones(strict.row(ix)) {
+			if right[iy] { continue; }
+			right[iy] = true;
+			if let Some(iz) = partner[iy] {
+				if !left[iz] {
+					left[iz] = true;
+					stack.push(iz);
+				}
+			}
+		}
+	}
+	// the cover consists of the unreached left and the reached right copies
+	let antichain: Vec<usize> = (0..n).filter(|&i| left[i] && !right[i]).collect();
+	Ok(elements(r, antichain.iter()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::relation::RelationVec;
+	use crate::relation::relation_vec::tests::{relation_arbitrary, domain_homogeneous};
+	use crate::relation::relation::tests::set;
+	use proptest::prelude::*;
+
+	#[test]
+	fn dilworth() {
+		// a < b < c, d < e, d < c, f
+		let r = RelationVec::from_predicate(&['a', 'b', 'c', 'd', 'e', 'f'], |(&x, &y)| {
+			[('a', 'b'), ('b', 'c'), ('d', 'e'), ('d', 'c')].contains(&(x, y))
+		});
+		assert_eq!(r.height(), Ok(3));
+		assert_eq!(r.longest_chain(), Ok(set("abc")));
+		assert_eq!(r.width(), Ok(3));
+		let antichain = r.largest_antichain().unwrap();
+		assert_eq!(antichain.cardinality(), 3);
+		assert!(antichain.contains(&SetElement::from(&'f')));
+		assert_eq!(r.chain_decomposition(), Ok(vec![set("abc"), set("de"), set("f")]));
+	}
+	#[test]
+	fn chain() {
+		let n10: Vec<u8> = (0..10).collect();
+		let r = RelationVec::from_predicate(&n10, |(x, y)| x <= y);
+		assert_eq!(r.height(), Ok(10));
+		assert_eq!(r.width(), Ok(1));
+		assert_eq!(r.chain_decomposition().unwrap().len(), 1);
+		let empty = RelationVec::from_predicate(&[0u8; 0], |_| true);
+		assert_eq!(empty.height(), Ok(0));
+		assert_eq!(empty.width(), Ok(0));
+		assert_eq!(empty.chain_decomposition(), Ok(vec![]));
+	}
+	#[test]
+	fn cycle() {
+		let r = RelationVec::from_predicate(&[0, 1, 2], |(&x, &y)| (x + 1) % 3 == y);
+		assert!(r.width().is_err());
+		assert!(r.height().is_err());
+		assert!(r.chain_decomposition().is_err());
+	}
+
+	proptest! {
+		#[test]
+		fn chains_and_antichains(r in relation_arbitrary(domain_homogeneous())) {
+			let order = RelationVec::closure_reflexive_transitive(&r);
+			assert_eq!(r.chain_decomposition().is_ok(), order.is_antisymmetric());
+			// the acyclic part of r
+			let mut r = r;
+			for ix in r.ixs() {
+				r.set_row(ix, (0..ix).map(|_| false));
+			}
+			let order = RelationVec::closure_reflexive_transitive(&r);
+			let comparable = |x: &SetElement, y: &SetElement| order.eval(x, y) || order.eval(y, x);
+			let chains = r.chain_decomposition().unwrap();
+			// the chains partition the domain
+			let union: Set = chains.iter().flat_map(Set::iter).cloned().collect();
+			assert_eq!(&union, r.get_domain().0);
+			assert_eq!(chains.iter().map(Set::cardinality).sum::<usize>(), union.cardinality());
+			for c in chains.iter().chain(std::iter::once(&r.longest_chain().unwrap())) {
+				assert!(c.iter().all(|x| c.iter().all(|y| comparable(x, y))));
+			}
+			let antichain = r.largest_antichain().unwrap();
+			assert!(antichain.iter().all(|x| antichain.iter().all(|y| x == y || !comparable(x, y))));
+			// Dilworth: both are optimal iff they have the same size
+			assert_eq!(antichain.cardinality(), chains.len());
+			let height = r.height().unwrap();
+			assert_eq!(r.longest_chain().unwrap().cardinality(), height);
+			assert!(chains.iter().all(|c| c.cardinality() <= height));
+		}
+	}
+}
@@ -5,11 +5,17 @@
 
 use std;
 
-use crate::set::Set;
-use crate::relation::Relation;
+use crate::set::{Set, SetElement};
+use crate::relation::{Relation, RelationMut, RelationBits, RelationVec};
+use crate::relation::lattice::{self, Lattice, LatticeError};
+use crate::relation::relation_bits::{ones, contains};
+use crate::relation::dilworth;
+use crate::relation::linear_extension::{self, Cycle, LinearExtensions, topological_sort, cycle};
+use crate::union_find::UnionFind;
 use crate::relation::relation::{
 	Empty, Universal,
-	Complement, Concatenation, Converse, Intersection, Union
+	Complement, Concatenation, Converse, Intersection, Union, Kronecker, Restriction,
+	LeftResidual, RightResidual, SymmetricQuotient,
 };
 
 /// cross!(1..4, a..d) = (
@@ -118,37 +124,145 @@ pub trait Endorelation : Relation {
 	/// A relation is equivalent if the relation is a pre-order and symmetric.
 	fn is_equivalent(&self) -> bool { self.is_preorder() && self.is_symmetric() }
 
-	/// Return `true` if the relation is difunctional.
-	/// A relation is difunctional iff `∀w,x,y,z ∈ X: xRy ∧ zRy ∧ zRw ⇒ xRw`
+	/// Return `true` if the relation is a lattice.
+	/// A relation is a lattice iff it is a partial order and every pair
+	/// of elements has a supremum and an infimum.
+	fn is_lattice(&self) -> bool {
+		self.lattice().is_ok()
+	}
+	/// Return the [`Lattice`] view of the relation, or the reason why the
+	/// relation is not a lattice.
+	fn lattice(&self) -> Result<Lattice<'_, Self>, LatticeError> {
+		Lattice::new(self)
+	}
+	/// Return `true` if the relation is a modular lattice.
+	/// A lattice is modular iff `∀a,b,c ∈ X: a ≤ c ⇒ a ∨ (b ∧ c) = (a ∨ b) ∧ c`
+	///
+	/// See [`Lattice::check_modular`] for a witness.
+	fn is_modular(&self) -> bool {
+		self.lattice().is_ok_and(|l| l.check_modular().is_ok())
+	}
+	/// Return `true` if the relation is a distributive lattice.
+	/// A lattice is distributive iff `∀a,b,c ∈ X: a ∧ (b ∨ c) = (a ∧ b) ∨ (a ∧ c)`
+	///
+	/// See [`Lattice::check_distributive`] for a witness.
+	fn is_distributive(&self) -> bool {
+		self.lattice().is_ok_and(|l| l.check_distributive().is_ok())
+	}
+	/// Return `true` if the relation is a complemented lattice.
+	/// A lattice is complemented iff `∀x ∈ X: ∃y ∈ X: x ∨ y = ⊤ ∧ x ∧ y = ⊥`
+	///
+	/// See [`Lattice::check_complemented`] for a witness.
+	fn is_complemented(&self) -> bool {
+		self.lattice().is_ok_and(|l| l.check_complemented().is_ok())
+	}
+	/// Return `true` if the relation is a relatively complemented lattice.
+	/// A lattice is relatively complemented iff every interval `[a, b]` is complemented.
+	///
+	/// See [`Lattice::check_relatively_complemented`] for a witness.
+	fn is_relatively_complemented(&self) -> bool {
+		self.lattice().is_ok_and(|l| l.check_relatively_complemented().is_ok())
+	}
+	/// Return `true` if the relation is a Boolean lattice.
+	/// A lattice is Boolean iff it is distributive and complemented.
+	///
+	/// See [`Lattice::check_boolean`] for a witness.
+	fn is_boolean(&self) -> bool {
+		self.lattice().is_ok_and(|l| l.check_boolean().is_ok())
+	}
+	/// The Dedekind–MacNeille completion of the partial order `r`, the
+	/// smallest complete lattice containing `r`, together with the embedding
+	/// of `r` into it. Returns `None` if `r` is not a partial order.
 	///
-	/// aka regular
-	fn is_difunctional(&self) -> bool {
+	/// See [`lattice::dedekind_macneille`] for details.
+	fn completion_dedekind_macneille<R: Endorelation>(r: &R) -> Option<(RelationVec, RelationVec)> {
+		lattice::dedekind_macneille(r)
+	}
+	/// Return `true` if the relation is a sublattice of `other`.
+	/// The relation is a sublattice of `other` iff `other` is a lattice,
+	/// the relation is `other` restricted to a subset of its domain and
+	/// that subset is closed under the join and meet of `other`.
+	fn is_sublattice<T: Endorelation>(&self, other: &T) -> bool {
 		debug_assert!(self.is_homogeneous());
-		for (ix, iy) in cross!(self.ixs(), self.iys()) {
-			if !self.eval_at(ix, iy) { continue; }
-			for iz in (ix+1)..self.get_domain().0.cardinality() {
-				if !self.eval_at(iz, iy) { continue; }
-				for iw in self.ixs() {
-					if self.eval_at(ix, iw) != self.eval_at(iz, iw) {
-						return false;
-					}
-				}
-			}
+		let (sub, sup) = (self.get_domain().0, other.get_domain().0);
+		if !sub.is_subset(sup) { return false; }
+		let indices = indices(other, sub);
+		let restricted = cross!(self.ixs(), self.iys()).all(
+			|(ix, iy)| self.eval_at(ix, iy) == other.eval_at(indices[ix], indices[iy])
+		);
+		restricted && other.has_sublattice(sub)
+	}
+	/// Return `true` if the given non-empty subset of the domain forms a
+	/// sublattice of the relation, i.e. if the relation is a lattice and the
+	/// subset is closed under its join and meet.
+	fn has_sublattice(&self, subset: &Set) -> bool {
+		match self.lattice() {
+			Ok(lattice) => lattice.is_sublattice(subset),
+			Err(_) => false,
 		}
-		return true;
 	}
 
-	/// Return `true` if the relation is a lattice.
-	fn is_lattice(&self) -> bool {
+	/// Return the minimal elements of the given subset of the domain.
+	/// An element `x ∈ U` is minimal iff `∀y ∈ U: yRx ⇒ xRy`
+	fn minimal_elements(&self, set: &Set) -> Set {
 		debug_assert!(self.is_homogeneous());
-		if !self.is_partial_order() { return false; } // TODO? Error
-		// TODO
-		false
+		let u = indices(self, set);
+		u.iter().cloned()
+			.filter(|&ix| u.iter().all(|&iy| !self.eval_at(iy, ix) || self.eval_at(ix, iy)))
+			.map(|ix| element(self, ix))
+			.collect()
 	}
-	/// Return `true` if the relation is a sublattice.
-	fn is_sublattice<T: Endorelation>(&self, other: &T) -> bool {
-		// TODO
-		false
+	/// Return the maximal elements of the given subset of the domain.
+	/// An element `x ∈ U` is maximal iff `∀y ∈ U: xRy ⇒ yRx`
+	fn maximal_elements(&self, set: &Set) -> Set {
+		debug_assert!(self.is_homogeneous());
+		let u = indices(self, set);
+		u.iter().cloned()
+			.filter(|&ix| u.iter().all(|&iy| !self.eval_at(ix, iy) || self.eval_at(iy, ix)))
+			.map(|ix| element(self, ix))
+			.collect()
+	}
+	/// Return the least element of the given subset of the domain, if it exists.
+	/// An element `x ∈ U` is least iff `∀y ∈ U: xRy`
+	///
+	/// Returns `None` if there is no or, in a pre-order, more than one least element.
+	fn least_element(&self, set: &Set) -> Option<SetElement> {
+		let u = indices(self, set);
+		unique(u.iter().cloned().filter(|&ix| u.iter().all(|&iy| self.eval_at(ix, iy))))
+			.map(|ix| element(self, ix))
+	}
+	/// Return the greatest element of the given subset of the domain, if it exists.
+	/// An element `x ∈ U` is greatest iff `∀y ∈ U: yRx`
+	///
+	/// Returns `None` if there is no or, in a pre-order, more than one greatest element.
+	fn greatest_element(&self, set: &Set) -> Option<SetElement> {
+		let u = indices(self, set);
+		unique(u.iter().cloned().filter(|&ix| u.iter().all(|&iy| self.eval_at(iy, ix))))
+			.map(|ix| element(self, ix))
+	}
+	/// Return the upper bounds of the given subset of the domain,
+	/// i.e. the set `{ x ∈ X: ∀y ∈ U: yRx }`
+	///
+	/// This is the strict image of `U`, see [`Relation::image_strict`].
+	fn upper_bounds(&self, set: &Set) -> Set {
+		self.image_strict(set)
+	}
+	/// Return the lower bounds of the given subset of the domain,
+	/// i.e. the set `{ x ∈ X: ∀y ∈ U: xRy }`
+	///
+	/// This is the strict pre-image of `U`, see [`Relation::preimage_strict`].
+	fn lower_bounds(&self, set: &Set) -> Set {
+		self.preimage_strict(set)
+	}
+	/// Return the supremum of the given subset of the domain, if it exists.
+	/// The supremum is the least element of the upper bounds.
+	fn supremum(&self, set: &Set) -> Option<SetElement> {
+		self.least_element(&self.upper_bounds(set))
+	}
+	/// Return the infimum of the given subset of the domain, if it exists.
+	/// The infimum is the greatest element of the lower bounds.
+	fn infimum(&self, set: &Set) -> Option<SetElement> {
+		self.greatest_element(&self.lower_bounds(set))
 	}
 
 	/// The identity `Relation I` where `xIy ⇔ x = y`
@@ -171,8 +285,204 @@ pub trait Endorelation : Relation {
 	 * Other closures are more efficiently implemented on the
 	 * incidence matrices directly.
 	 */
-	//fn closure_difunctional<R: Endorelation>(r: &R) -> R {}
-	//fn closure_biorder<R: Endorelation>(r: &R) -> R {}
+	/// Transitive closure: the smallest transitive relation containing `r`,
+	/// also denoted `r⁺`.
+	///
+	/// Calculated with a word-parallel variant of Warshall's algorithm.
+	fn closure_transitive<R: Endorelation>(r: &R) -> RelationBits {
+		debug_assert!(r.is_homogeneous());
+		let mut bits = RelationBits::from_relation(r);
+		bits.warshall();
+		bits
+	}
+	/// Reflexive-transitive closure: the smallest pre-order containing `r`,
+	/// also denoted `r*`.
+	///
+	/// Calculated with a word-parallel variant of Warshall's algorithm.
+	fn closure_reflexive_transitive<R: Endorelation>(r: &R) -> RelationBits {
+		debug_assert!(r.is_homogeneous());
+		let mut bits = RelationBits::from_relation(r);
+		for i in r.ixs() {
+			bits.set_at(i, i, true);
+		}
+		bits.warshall();
+		bits
+	}
+	/// Equivalence closure: the smallest equivalence relation containing `r`.
+	///
+	/// See [`Endorelation::equivalence_classes`] for the resulting partition.
+	fn closure_equivalence<R: Endorelation>(r: &R) -> RelationBits {
+		debug_assert!(r.is_homogeneous());
+		let mut bits = RelationBits::new((r.get_domain().0.clone(), r.get_domain().1.clone()));
+		for class in partition(r) {
+			for &ix in class.iter() {
+				for &iy in class.iter() {
+					bits.set_at(ix, iy, true);
+				}
+			}
+		}
+		bits
+	}
+	/// The classes of the equivalence closure of `r`.
+	///
+	/// The classes partition the domain of `r` and are ordered by their
+	/// smallest element.
+	fn equivalence_classes<R: Endorelation>(r: &R) -> Vec<Set> {
+		debug_assert!(r.is_homogeneous());
+		let elements: Vec<_> = r.get_domain().0.iter().collect();
+		partition(r).iter()
+			.map(|class| class.iter().map(|&i| elements[i]).cloned().collect())
+			.collect()
+	}
+
+	/// Return a linear extension of the relation, i.e. a topological sort
+	/// of its domain, or a cycle which prevents it.
+	/// Among all linear extensions, the one with the smallest indices first is chosen.
+	///
+	/// See the [`linear_extension`] module for details.
+	fn linear_extension(&self) -> Result<Vec<SetElement>, Cycle> {
+		let elements: Vec<&SetElement> = self.get_domain().0.iter().collect();
+		topological_sort(self)
+			.map(|order| order.into_iter().map(|i| elements[i].clone()).collect())
+			.map_err(|c| cycle(self, c))
+	}
+	/// Return an iterator over all linear extensions of the relation,
+	/// or a cycle which prevents them.
+	fn linear_extensions(&self) -> Result<LinearExtensions<'_, Self>, Cycle> {
+		LinearExtensions::new(self)
+	}
+	/// Return the number of linear extensions of the relation,
+	/// or a cycle which prevents them.
+	///
+	/// The time and memory needed grow with the number of down-sets, i.e.
+	/// exponentially with the width of the order.
+	fn count_linear_extensions(&self) -> Result<u128, Cycle> {
+		topological_sort(self).map_err(|c| cycle(self, c))?;
+		Ok(linear_extension::count(self))
+	}
+
+	/// Return the height of the partial order generated by the relation,
+	/// i.e. the size of its longest chain, or a cycle which prevents the order.
+	fn height(&self) -> Result<usize, Cycle> {
+		self.longest_chain().map(|c| c.cardinality())
+	}
+	/// Return a longest chain of the partial order generated by the relation,
+	/// or a cycle which prevents the order.
+	/// A chain is a set of pairwise comparable elements.
+	fn longest_chain(&self) -> Result<Set, Cycle> {
+		dilworth::longest_chain(self)
+	}
+	/// Return the width of the partial order generated by the relation,
+	/// i.e. the size of its largest antichain, or a cycle which prevents the order.
+	fn width(&self) -> Result<usize, Cycle> {
+		self.largest_antichain().map(|a| a.cardinality())
+	}
+	/// Return a largest antichain of the partial order generated by the relation,
+	/// or a cycle which prevents the order.
+	/// An antichain is a set of pairwise incomparable elements.
+	fn largest_antichain(&self) -> Result<Set, Cycle> {
+		dilworth::largest_antichain(self)
+	}
+	/// Return a smallest partition of the partial order generated by the
+	/// relation into chains, or a cycle which prevents the order.
+	///
+	/// By Dilworth's theorem, the number of chains equals the width.
+	fn chain_decomposition(&self) -> Result<Vec<Set>, Cycle> {
+		dilworth::chain_decomposition(self)
+	}
+
+	/// Transitive reduction: the smallest relation with the same transitive
+	/// closure as `r`, also denoted `r⁻`.
+	///
+	/// The transitive reduction is unique if `r` is acyclic.
+	/// Loops of `r` are kept, any other cycle yields `None`.
+	fn transitive_reduction<R: Endorelation>(r: &R) -> Option<RelationBits> {
+		debug_assert!(r.is_homogeneous());
+		let mut strict = R::closure_transitive(r);
+		for i in r.ixs() {
+			strict.set_at(i, i, false);
+		}
+		let mut reduction = RelationBits::new((r.get_domain().0.clone(), r.get_domain().1.clone()));
+		for ix in r.ixs() {
+			// successors of ix reachable through another successor
+			let mut implied = vec![0u64; strict.row(ix).len()];
+			for iz in ones(strict.row(ix)) {
+				if strict.eval_at(iz, ix) { return None; }
+				for (w, &v) in implied.iter_mut().zip(strict.row(iz)) {
+					*w |= v;
+				}
+			}
+			for iy in ones(strict.row(ix)) {
+				if !contains(&implied, iy) {
+					reduction.set_at(ix, iy, true);
+				}
+			}
+			if r.eval_at(ix, ix) {
+				reduction.set_at(ix, ix, true);
+			}
+		}
+		Some(reduction)
+	}
+	/// The Hasse diagram of the partial order `r`, i.e. its cover relation.
+	/// An element `y` covers `x` iff `x < y` and there is no `z` with `x < z < y`.
+	///
+	/// Returns `None` if `r` is not a partial order.
+	/// See [`Endorelation::covering_pairs`] for the list of covering pairs.
+	fn hasse_diagram<R: Endorelation>(r: &R) -> Option<RelationBits> {
+		if !r.is_partial_order() { return None; }
+		let mut strict = RelationBits::from_relation(r);
+		for i in r.ixs() {
+			strict.set_at(i, i, false);
+		}
+		RelationBits::transitive_reduction(&strict)
+	}
+	/// The pairs `(x, y)` of the partial order where `y` covers `x`,
+	/// ordered like the domain.
+	///
+	/// Returns `None` if the relation is not a partial order.
+	/// See [`Endorelation::hasse_diagram`] for the cover relation.
+	fn covering_pairs(&self) -> Option<Vec<(SetElement, SetElement)>> {
+		let hasse = Self::hasse_diagram(self)?;
+		let elements: Vec<&SetElement> = self.get_domain().0.iter().collect();
+		let pairs = cross!(self.ixs(), self.iys())
+			.filter(|&(ix, iy)| hasse.eval_at(ix, iy))
+			.map(|(ix, iy)| (elements[ix].clone(), elements[iy].clone()))
+			.collect();
+		Some(pairs)
+	}
+}
+
+/// The indices of the elements of `set` in the domain of `r`.
+fn indices<R: Relation>(r: &R, set: &Set) -> Vec<usize> {
+	debug_assert!(set.is_subset(r.get_domain().0));
+	Set::intersection_enumerated(r.get_domain().0, set)
+		.map(|((i, _), _)| i)
+		.collect()
+}
+
+/// The element at index `i` in the domain of `r`.
+fn element<R: Relation>(r: &R, i: usize) -> SetElement {
+	r.get_domain().0.iter().nth(i).unwrap().clone()
+}
+
+/// The only item of `iter`, if it has exactly one.
+fn unique<I: Iterator>(mut iter: I) -> Option<I::Item> {
+	match (iter.next(), iter.next()) {
+		(Some(item), None) => Some(item),
+		_ => None,
+	}
+}
+
+/// Partition the indices of the domain of `r` into the connected components
+/// of `r`, i.e. the classes of its equivalence closure.
+fn partition<R: Relation>(r: &R) -> Vec<Vec<usize>> {
+	let mut uf = UnionFind::new(r.get_domain().0.cardinality());
+	for (ix, iy) in cross!(r.ixs(), r.iys()) {
+		if r.eval_at(ix, iy) {
+			uf.union(ix, iy);
+		}
+	}
+	uf.sets()
 }
 
 impl Endorelation for Empty<'_> {}
@@ -194,14 +504,19 @@ impl Endorelation for Identity<'_> {}
 
 impl<R: Relation> Endorelation for Complement<'_, R> {}
 impl<P: Relation, Q: Relation> Endorelation for Concatenation<'_, P, Q> {}
+impl<P: Relation, Q: Relation> Endorelation for LeftResidual<'_, P, Q> {}
+impl<P: Relation, Q: Relation> Endorelation for RightResidual<'_, P, Q> {}
+impl<P: Relation, Q: Relation> Endorelation for SymmetricQuotient<'_, P, Q> {}
 impl<R: Relation> Endorelation for Converse<'_, R> {}
 impl<P: Relation, Q: Relation> Endorelation for Intersection<'_, P, Q> {}
 impl<P: Relation, Q: Relation> Endorelation for Union<'_, P, Q> {}
+impl<P: Relation, Q: Relation> Endorelation for Kronecker<'_, P, Q> {}
+impl<R: Relation> Endorelation for Restriction<'_, R> {}
 
 #[cfg(test)]
 pub mod tests {
 	use super::*;
-	use crate::relation;
+	use crate::relation::{self, RelationVec};
 
 	pub fn endorelation_property_test<R>(r: &R)
 	where R: Endorelation + std::fmt::Debug
@@ -215,4 +530,113 @@ pub mod tests {
 		assert_eq!(r.is_partial_order(), r.is_preorder() && r.is_antisymmetric());
 		assert_eq!(r.is_equivalent(), r.is_preorder() && r.is_symmetric());
 	}
+
+	pub fn closures<R>(r: &R)
+	where R: Endorelation + std::fmt::Debug
+	{
+		// naive transitive closure: r ∪ r;r ∪ r;r;r ∪ ...
+		let mut expected = RelationVec::from_relation(r);
+		loop {
+			let next = RelationVec::from_relation(&R::union(&expected, &R::concatenation(&expected, r)));
+			if next == expected { break; }
+			expected = next;
+		}
+		let t = R::closure_transitive(r);
+		assert!(t.is_transitive());
+		assert!(relation::eq(&t, &expected));
+
+		let rt = R::closure_reflexive_transitive(r);
+		assert!(rt.is_preorder());
+		assert!(relation::eq(&rt, &R::closure_reflexive(&t)));
+		// closures are idempotent
+		assert!(relation::eq(&R::closure_transitive(&t), &t));
+		assert!(relation::eq(&R::closure_reflexive_transitive(&rt), &rt));
+
+		// equivalence closure: reflexive-transitive closure of the symmetric closure
+		let e = R::closure_equivalence(r);
+		assert!(e.is_equivalent());
+		assert!(relation::eq(&e, &R::closure_reflexive_transitive(&R::closure_symmetric(r))));
+		// its classes partition the domain and are related exactly within each class
+		let classes = R::equivalence_classes(r);
+		assert_eq!(classes.iter().map(Set::cardinality).sum::<usize>(), r.get_domain().0.cardinality());
+		for class in classes.iter() {
+			let x = class.iter().next().unwrap();
+			assert_eq!(&e.image(&std::iter::once(x.clone()).collect()), class);
+		}
+	}
+
+	pub fn reductions<R>(r: &R)
+	where R: Endorelation + std::fmt::Debug
+	{
+		// the acyclic part of r
+		let mut acyclic = RelationBits::from_relation(r);
+		for (ix, iy) in cross!(r.ixs(), r.iys()).filter(|(ix, iy)| ix > iy) {
+			acyclic.set_at(ix, iy, false);
+		}
+		let reduction = R::transitive_reduction(&acyclic).unwrap();
+		let closure = R::closure_transitive(&acyclic);
+		assert!(relation::eq(&R::closure_transitive(&reduction), &closure));
+		assert!(relation::eq(&R::intersection(&reduction, &acyclic), &reduction));
+		// no pair of the reduction can be removed
+		for (ix, iy) in cross!(r.ixs(), r.iys()).filter(|&(ix, iy)| reduction.eval_at(ix, iy)) {
+			let mut smaller = reduction.clone();
+			smaller.set_at(ix, iy, false);
+			assert!(!relation::eq(&R::closure_transitive(&smaller), &closure));
+		}
+		// cycles through distinct elements have no reduction
+		let t = R::closure_transitive(r);
+		let cyclic = cross!(r.ixs(), r.iys())
+			.any(|(ix, iy)| ix != iy && t.eval_at(ix, iy) && t.eval_at(iy, ix));
+		assert_eq!(R::transitive_reduction(r).is_none(), cyclic);
+
+		// the Hasse diagram of a partial order is the reduction of its strict part
+		let order = R::closure_reflexive_transitive(&acyclic);
+		let hasse = R::hasse_diagram(&order).unwrap();
+		let strict = R::transitive_reduction(&R::closure_transitive(&acyclic)).unwrap();
+		let id = R::identity(r.get_domain());
+		let not_id = R::complement(&id);
+		let strict_reduction = R::intersection(&strict, &not_id);
+		assert!(relation::eq(&hasse, &strict_reduction));
+		assert_eq!(order.covering_pairs().unwrap().len(), hasse.count());
+		assert_eq!(R::hasse_diagram(r).is_some(), r.is_partial_order());
+	}
+
+	pub fn bounds<R>(r: &R)
+	where R: Endorelation + std::fmt::Debug
+	{
+		// the partial order generated by the pairs of r above the diagonal
+		let mut upper = RelationBits::from_relation(r);
+		for (ix, iy) in cross!(r.ixs(), r.iys()).filter(|(ix, iy)| ix >= iy) {
+			upper.set_at(ix, iy, false);
+		}
+		let order = RelationBits::closure_reflexive_transitive(&upper);
+		assert!(order.is_partial_order());
+
+		let domain = r.get_domain().0;
+		let evens: Set = domain.iter().step_by(2).cloned().collect();
+		for set in [domain.clone(), evens, Set::new()].iter() {
+			let (minimal, maximal) = (order.minimal_elements(set), order.maximal_elements(set));
+			assert!(minimal.is_subset(set) && maximal.is_subset(set));
+			assert_eq!(set.cardinality() == 0, minimal.cardinality() == 0);
+			// a least element is the only minimal element
+			if let Some(x) = order.least_element(set) {
+				assert!(set.contains(&x) && order.lower_bounds(set).contains(&x));
+				assert_eq!(minimal, std::iter::once(x).collect());
+			}
+			if let Some(x) = order.greatest_element(set) {
+				assert!(set.contains(&x) && order.upper_bounds(set).contains(&x));
+				assert_eq!(maximal, std::iter::once(x).collect());
+			}
+			// bounds of the converse order are swapped
+			let conv = R::converse(&order);
+			assert_eq!(order.upper_bounds(set), conv.lower_bounds(set));
+			assert_eq!(order.supremum(set), conv.infimum(set));
+			assert_eq!(order.minimal_elements(set), conv.maximal_elements(set));
+			if let Some(x) = order.supremum(set) {
+				let upper_bounds = order.upper_bounds(set);
+				assert!(upper_bounds.contains(&x));
+				assert!(upper_bounds.iter().all(|y| order.eval(&x, y)));
+			}
+		}
+	}
 }
@@ -0,0 +1,604 @@
+//! A [`Lattice`] view of a partial order.
+//!
+//! A partial order `≤` is a lattice iff every pair of elements `x, y`
+//! has a supremum (join) `x ∨ y` and an infimum (meet) `x ∧ y`.
+
+use std::collections::BTreeSet;
+use std::error;
+use std::fmt;
+use std::vec::Vec;
+
+use crate::set::{Set, SetElement};
+use crate::relation::{Endorelation, RelationVec};
+
+/// The reason why a relation is not a [`Lattice`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LatticeError {
+	/// The relation is not a partial order.
+	NotPartialOrder,
+	/// The domain of the relation is empty.
+	Empty,
+	/// The pair of elements has no supremum.
+	NoSupremum(SetElement, SetElement),
+	/// The pair of elements has no infimum.
+	NoInfimum(SetElement, SetElement),
+}
+
+impl fmt::Display for LatticeError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			LatticeError::NotPartialOrder => write!(f, "relation is not a partial order"),
+			LatticeError::Empty => write!(f, "relation has an empty domain"),
+			LatticeError::NoSupremum(x, y) => write!(f, "{} and {} have no supremum", x, y),
+			LatticeError::NoInfimum(x, y) => write!(f, "{} and {} have no infimum", x, y),
+		}
+	}
+}
+
+impl error::Error for LatticeError {}
+
+/// A witness why a lattice does not belong to a class of lattices.
+///
+/// Embedded sublattices are given as `[0, a, b, c, 1]`, ordered from the
+/// bottom to the top of the sublattice.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LatticeWitness {
+	/// The relation is not a lattice at all.
+	NotLattice(LatticeError),
+	/// An embedded pentagon `N₅` with `0 < a < b < 1` and `c` incomparable to `a` and `b`.
+	/// Witnesses that the lattice is neither modular nor distributive.
+	N5(Box<[SetElement; 5]>),
+	/// An embedded diamond `M₃` with the pairwise incomparable `a`, `b` and `c`.
+	/// Witnesses that the lattice is not distributive.
+	M3(Box<[SetElement; 5]>),
+	/// An element without a complement.
+	NoComplement(SetElement),
+	/// An element of the interval `[a, b]` without a complement relative to it.
+	NoRelativeComplement(SetElement, (SetElement, SetElement)),
+}
+
+impl fmt::Display for LatticeWitness {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			LatticeWitness::NotLattice(e) => write!(f, "{}", e),
+			LatticeWitness::N5(w) => write!(f, "N5 {{{} {} {} {} {}}}", w[0], w[1], w[2], w[3], w[4]),
+			LatticeWitness::M3(w) => write!(f, "M3 {{{} {} {} {} {}}}", w[0], w[1], w[2], w[3], w[4]),
+			LatticeWitness::NoComplement(x) => write!(f, "{} has no complement", x),
+			LatticeWitness::NoRelativeComplement(x, (a, b)) => {
+				write!(f, "{} has no complement relative to [{}, {}]", x, a, b)
+			}
+		}
+	}
+}
+
+impl error::Error for LatticeWitness {}
+
+impl From<LatticeError> for LatticeWitness {
+	fn from(e: LatticeError) -> Self {
+		LatticeWitness::NotLattice(e)
+	}
+}
+
+/// A lattice, given as a partial order `R` where `xRy ⇔ x ≤ y`.
+///
+/// The join and meet of all pairs of elements are calculated once
+/// on construction.
+#[derive(Clone, Debug)]
+pub struct Lattice<'a, R: Endorelation> {
+	r: &'a R,
+	elements: Vec<&'a SetElement>,
+	join: Vec<usize>,
+	meet: Vec<usize>,
+	top: usize,
+	bottom: usize,
+}
+
+impl<'a, R: Endorelation> Lattice<'a, R> {
+	/// Create the lattice of the partial order `r` or return the reason
+	/// why `r` is not a lattice.
+	pub fn new(r: &'a R) -> Result<Self, LatticeError> {
+		debug_assert!(r.is_homogeneous());
+		if !r.is_partial_order() {
+			return Err(LatticeError::NotPartialOrder);
+		}
+		let elements: Vec<&SetElement> = r.get_domain().0.iter().collect();
+		let n = elements.len();
+		if n == 0 {
+			return Err(LatticeError::Empty);
+		}
+		let mut join = vec![0; n * n];
+		let mut meet = vec![0; n * n];
+		for ix in 0..n {
+			for iy in ix..n {
+				let j = least(r, (0..n).filter(|&iz| r.eval_at(ix, iz) && r.eval_at(iy, iz)))
+					.ok_or_else(|| LatticeError::NoSupremum(elements[ix].clone(), elements[iy].clone()))?;
+				let m = greatest(r, (0..n).filter(|&iz| r.eval_at(iz, ix) && r.eval_at(iz, iy)))
+					.ok_or_else(|| LatticeError::NoInfimum(elements[ix].clone(), elements[iy].clone()))?;
+				join[ix * n + iy] = j;
+				join[iy * n + ix] = j;
+				meet[ix * n + iy] = m;
+				meet[iy * n + ix] = m;
+			}
+		}
+		// every pair has a join and meet, so do all elements together
+		let top = (0..n).fold(0, |t, i| join[t * n + i]);
+		let bottom = (0..n).fold(0, |b, i| meet[b * n + i]);
+		Ok(Lattice { r, elements, join, meet, top, bottom })
+	}
+
+	/// The partial order of the lattice.
+	pub fn relation(&self) -> &'a R {
+		self.r
+	}
+
+	/// The supremum `x ∨ y` of the elements at index `ix` and `iy`.
+	pub fn join_at(&self, ix: usize, iy: usize) -> usize {
+		self.join[ix * self.elements.len() + iy]
+	}
+	/// The infimum `x ∧ y` of the elements at index `ix` and `iy`.
+	pub fn meet_at(&self, ix: usize, iy: usize) -> usize {
+		self.meet[ix * self.elements.len() + iy]
+	}
+	/// The index of the greatest element.
+	pub fn top_at(&self) -> usize {
+		self.top
+	}
+	/// The index of the least element.
+	pub fn bottom_at(&self) -> usize {
+		self.bottom
+	}
+
+	/// The supremum `x ∨ y`.
+	pub fn join(&self, x: &SetElement, y: &SetElement) -> &'a SetElement {
+		self.elements[self.join_at(self.index(x), self.index(y))]
+	}
+	/// The infimum `x ∧ y`.
+	pub fn meet(&self, x: &SetElement, y: &SetElement) -> &'a SetElement {
+		self.elements[self.meet_at(self.index(x), self.index(y))]
+	}
+	/// The greatest element `⊤`.
+	pub fn top(&self) -> &'a SetElement {
+		self.elements[self.top]
+	}
+	/// The least element `⊥`.
+	pub fn bottom(&self) -> &'a SetElement {
+		self.elements[self.bottom]
+	}
+
+	/// Return `true` if the non-empty `subset` of the domain is a sublattice,
+	/// i.e. if it is closed under join and meet.
+	/// Subsets with elements outside of the domain are no sublattices.
+	pub fn is_sublattice(&self, subset: &Set) -> bool {
+		if !subset.is_subset(self.r.get_domain().0) { return false; }
+		let indices: Vec<usize> = Set::intersection_enumerated(self.r.get_domain().0, subset)
+			.map(|((i, _), _)| i)
+			.collect();
+		if indices.is_empty() { return false; }
+		let mut member = vec![false; self.elements.len()];
+		for &i in indices.iter() {
+			member[i] = true;
+		}
+		indices.iter().all(
+			|&ix| indices.iter().all(
+				|&iy| member[self.join_at(ix, iy)] && member[self.meet_at(ix, iy)]
+			)
+		)
+	}
+
+	/// Check if the lattice is modular, i.e. if
+	/// `a ≤ c ⇒ a ∨ (b ∧ c) = (a ∨ b) ∧ c` for all `a, b, c`.
+	/// Otherwise return an embedded `N₅`.
+	pub fn check_modular(&self) -> Result<(), LatticeWitness> {
+		match self.find_n5() {
+			Some(w) => Err(LatticeWitness::N5(self.witness(w))),
+			None => Ok(()),
+		}
+	}
+	/// Check if the lattice is distributive, i.e. if
+	/// `a ∧ (b ∨ c) = (a ∧ b) ∨ (a ∧ c)` for all `a, b, c`.
+	/// Otherwise return an embedded `N₅` or `M₃`.
+	pub fn check_distributive(&self) -> Result<(), LatticeWitness> {
+		let n = self.elements.len();
+		let (j, m) = (|x, y| self.join_at(x, y), |x, y| self.meet_at(x, y));
+		for (a, b, c) in (0..n).flat_map(|a| (0..n).flat_map(move |b| (0..n).map(move |c| (a, b, c)))) {
+			if m(a, j(b, c)) == j(m(a, b), m(a, c)) { continue; }
+			self.check_modular()?;
+			// a modular lattice contains an M₃ spanned by the failing triple
+			let d = j(j(m(a, b), m(b, c)), m(c, a));
+			let e = m(m(j(a, b), j(b, c)), j(c, a));
+			let w = [d, j(m(a, e), d), j(m(b, e), d), j(m(c, e), d), e];
+			return Err(LatticeWitness::M3(self.witness(w)));
+		}
+		Ok(())
+	}
+	/// Check if the lattice is complemented, i.e. if every element `x`
+	/// has a complement `y` with `x ∨ y = ⊤` and `x ∧ y = ⊥`.
+	/// Otherwise return an element without a complement.
+	pub fn check_complemented(&self) -> Result<(), LatticeWitness> {
+		match self.find_uncomplemented(self.bottom, self.top) {
+			Some(x) => Err(LatticeWitness::NoComplement(self.elements[x].clone())),
+			None => Ok(()),
+		}
+	}
+	/// Check if the lattice is relatively complemented, i.e. if every
+	/// interval `[a, b]` is complemented.
+	/// Otherwise return an element without a complement relative to an interval.
+	pub fn check_relatively_complemented(&self) -> Result<(), LatticeWitness> {
+		let n = self.elements.len();
+		for a in 0..n {
+			for b in (0..n).filter(|&b| self.r.eval_at(a, b)) {
+				if let Some(x) = self.find_uncomplemented(a, b) {
+					let interval = (self.elements[a].clone(), self.elements[b].clone());
+					return Err(LatticeWitness::NoRelativeComplement(self.elements[x].clone(), interval));
+				}
+			}
+		}
+		Ok(())
+	}
+	/// Check if the lattice is Boolean, i.e. complemented and distributive.
+	pub fn check_boolean(&self) -> Result<(), LatticeWitness> {
+		self.check_distributive()?;
+		self.check_complemented()
+	}
+
+	/// Search an `N₅` as `[0, a, b, c, 1]`.
+	fn find_n5(&self) -> Option<[usize; 5]> {
+		let n = self.elements.len();
+		let (j, m) = (|x, y| self.join_at(x, y), |x, y| self.meet_at(x, y));
+		for a in 0..n {
+			for c in (0..n).filter(|&c| self.r.eval_at(a, c)) {
+				for b in 0..n {
+					let u = j(a, m(b, c));
+					let v = m(j(a, b), c);
+					if u != v {
+						// b ∧ u = b ∧ v = b ∧ c and b ∨ u = b ∨ v = a ∨ b
+						return Some([m(b, c), u, v, b, j(a, b)]);
+					}
+				}
+			}
+		}
+		None
+	}
+	/// Search an element of the interval `[a, b]` without a complement relative to it.
+	fn find_uncomplemented(&self, a: usize, b: usize) -> Option<usize> {
+		let interval: Vec<usize> = (0..self.elements.len())
+			.filter(|&x| self.r.eval_at(a, x) && self.r.eval_at(x, b))
+			.collect();
+		interval.iter().cloned().find(
+			|&x| !interval.iter().any(|&y| self.join_at(x, y) == b && self.meet_at(x, y) == a)
+		)
+	}
+	fn witness(&self, w: [usize; 5]) -> Box<[SetElement; 5]> {
+		let e = |i: usize| self.elements[w[i]].clone();
+		Box::new([e(0), e(1), e(2), e(3), e(4)])
+	}
+
+	fn index(&self, x: &SetElement) -> usize {
+		self.elements.iter().position(|&e| e == x).unwrap()
+	}
+}
+
+/// The Dedekind–MacNeille completion of the partial order `r`, i.e. the
+/// smallest complete lattice containing `r`, or `None` if `r` is not a
+/// partial order.
+///
+/// The elements of the completion are the cuts `A = (A↑)↓` of `r`, where
+/// `A↑` and `A↓` are the upper and lower bounds of `A`, ordered by inclusion.
+/// Every cut is given as a [`SetElement::Set`] of the elements of `A`.
+/// The embedding relates every element `x` to its principal cut `{x}↓`.
+pub fn dedekind_macneille<R: Endorelation>(r: &R) -> Option<(RelationVec, RelationVec)> {
+	if !r.is_partial_order() { return None; }
+	let domain = r.get_domain().0;
+	let principal = |x: &SetElement| r.lower_bounds(&std::iter::once(x.clone()).collect());
+	// the cuts are exactly the intersections of principal cuts
+	let mut cuts: BTreeSet<Set> = std::iter::once(domain.clone()).collect();
+	for x in domain.iter() {
+		let ideal = principal(x);
+		let intersections: Vec<Set> = cuts.iter()
+			.map(|a| a.intersection(&ideal).cloned().collect())
+			.collect();
+		cuts.extend(intersections);
+	}
+	let completion: Set = cuts.iter().cloned().collect();
+	let cut = |a: &SetElement| match a {
+		SetElement::Set(a) => a.clone(),
+		_ => unreachable!(),
+	};
+	let cuts: Vec<Set> = completion.iter().map(cut).collect();
+	let order = cross(&cuts, &cuts, |a, b| a.is_subset(b));
+	let ideals: Vec<Set> = domain.iter().map(principal).collect();
+	let embedding = cross(&ideals, &cuts, |ideal, a| ideal == a);
+	Some((
+		RelationVec::new((completion.clone(), completion.clone()), order),
+		RelationVec::new((domain.clone(), completion), embedding),
+	))
+}
+/// The incidence matrix of `predicate` over `xs × ys`.
+fn cross<F: Fn(&Set, &Set) -> bool>(xs: &[Set], ys: &[Set], predicate: F) -> Vec<bool> {
+	xs.iter().flat_map(|x| ys.iter().map(|y| predicate(x, y)).collect::<Vec<_>>()).collect()
+}
+
+/// Return the least element of `candidates` under `r`, if there is one.
+fn least<R: Endorelation, I: Iterator<Item = usize> + Clone>(r: &R, candidates: I) -> Option<usize> {
+	let mut it = candidates.clone();
+	let first = it.next()?;
+	let m = it.fold(first, |m, c| if r.eval_at(c, m) { c } else { m });
+	if candidates.into_iter().all(|c| r.eval_at(m, c)) { Some(m) } else { None }
+}
+/// Return the greatest element of `candidates` under `r`, if there is one.
+fn greatest<R: Endorelation, I: Iterator<Item = usize> + Clone>(r: &R, candidates: I) -> Option<usize> {
+	let mut it = candidates.clone();
+	let first = it.next()?;
+	let m = it.fold(first, |m, c| if r.eval_at(m, c) { c } else { m });
+	if candidates.into_iter().all(|c| r.eval_at(c, m)) { Some(m) } else { None }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::relation::Relation;
+	use proptest::{prelude::*, collection::vec};
+
+	fn divisibility(n: &[u8]) -> RelationVec {
+		RelationVec::from_predicate(n, |(&x, &y)| y % x == 0)
+	}
+	fn e(i: i32) -> SetElement {
+		SetElement::from(i)
+	}
+
+	#[test]
+	fn divisors_of_6() {
+		let r = divisibility(&[1, 2, 3, 6]);
+		let l = Lattice::new(&r).unwrap();
+		assert_eq!(l.join(&e(2), &e(3)), &e(6));
+		assert_eq!(l.meet(&e(2), &e(3)), &e(1));
+		assert_eq!(l.join(&e(1), &e(3)), &e(3));
+		assert_eq!(l.meet(&e(6), &e(2)), &e(2));
+		assert_eq!(l.top(), &e(6));
+		assert_eq!(l.bottom(), &e(1));
+		assert!(r.is_lattice());
+	}
+	#[test]
+	fn no_supremum() {
+		let r = divisibility(&[1, 2, 3]);
+		assert_eq!(Lattice::new(&r).unwrap_err(), LatticeError::NoSupremum(e(2), e(3)));
+		assert!(!r.is_lattice());
+	}
+	#[test]
+	fn no_infimum() {
+		let r = divisibility(&[2, 3, 6]);
+		let err = Lattice::new(&r).unwrap_err();
+		assert_eq!(err, LatticeError::NoInfimum(e(2), e(3)));
+		assert_eq!(err.to_string(), "2 and 3 have no infimum");
+	}
+	#[test]
+	fn not_partial_order() {
+		let r = RelationVec::from_predicate(&[1, 2, 3], |(x, y)| x < y);
+		assert_eq!(Lattice::new(&r).unwrap_err(), LatticeError::NotPartialOrder);
+	}
+	#[test]
+	fn empty() {
+		let r = RelationVec::from_predicate::<u8, _>(&[], |_| true);
+		assert_eq!(Lattice::new(&r).unwrap_err(), LatticeError::Empty);
+	}
+	#[test]
+	fn sublattice() {
+		// ordered like the domain
+		let r = divisibility(&[1, 12, 2, 3, 4, 6]);
+		let l = Lattice::new(&r).unwrap();
+		let set = |v: Vec<i32>| v.into_iter().collect::<Set>();
+		assert!(l.is_sublattice(&set(vec![1, 2, 3, 6])));
+		assert!(l.is_sublattice(&set(vec![4])));
+		assert!(!l.is_sublattice(&set(vec![])));
+		assert!(!l.is_sublattice(&set(vec![1, 2, 99])));
+		assert!(!r.has_sublattice(&set(vec![1, 2, 99])));
+		// 2 ∧ 3 = 1
+		assert!(!l.is_sublattice(&set(vec![2, 3, 6])));
+		// a lattice on its own, but 2 ∨ 3 = 6
+		assert!(!l.is_sublattice(&set(vec![1, 2, 3, 12])));
+		assert!(!r.has_sublattice(&set(vec![1, 2, 3, 12])));
+
+		let s = divisibility(&[1, 2, 3, 6]);
+		assert!(s.is_sublattice(&r));
+		let s = divisibility(&[1, 12, 2, 3]);
+		assert!(s.is_lattice());
+		assert!(!s.is_sublattice(&r));
+		// not the order of r restricted to {1, 2, 3, 6}
+		let s = RelationVec::from_predicate(&[1, 2, 3, 6], |(&x, &y)| x <= y);
+		assert!(s.is_lattice());
+		assert!(!s.is_sublattice(&r));
+		// not a subset of the domain of r
+		let s = divisibility(&[1, 5]);
+		assert!(!s.is_sublattice(&r));
+	}
+	/// The lattice of all subsets of `{0, ..., 4}` in the closure system
+	/// generated by the sets in `family`, ordered by inclusion.
+	fn closure_system(family: &[u8]) -> RelationVec {
+		let mut sets: Vec<u8> = vec![0b11111];
+		for &f in family.iter() {
+			let new: Vec<u8> = sets.iter().map(|&s| s & f).collect();
+			sets.extend(new);
+			sets.sort_unstable();
+			sets.dedup();
+		}
+		// ordered like the domain
+		sets.sort_by_key(|s| s.to_string());
+		RelationVec::from_predicate(&sets, |(&x, &y)| x & y == x)
+	}
+	/// Assert that `w` is an embedded `N₅` or `M₃` of `l`.
+	fn assert_sublattice_witness<R: Endorelation>(l: &Lattice<'_, R>, w: &LatticeWitness) {
+		let (w, n5) = match w {
+			LatticeWitness::N5(w) => (w, true),
+			LatticeWitness::M3(w) => (w, false),
+			w => panic!("unexpected witness {}", w),
+		};
+		let [o, a, b, c, i] = &**w;
+		for (x, y) in [(a, b), (a, c), (b, c)].iter() {
+			assert_ne!(x, y);
+		}
+		if n5 {
+			assert_eq!(l.join(a, b), b);
+			assert_eq!(l.meet(a, b), a);
+		} else {
+			assert_eq!(l.join(a, b), i);
+			assert_eq!(l.meet(a, b), o);
+		}
+		for x in [a, b].iter() {
+			assert_eq!(l.join(x, c), i);
+			assert_eq!(l.meet(x, c), o);
+		}
+	}
+
+	#[test]
+	fn n5() {
+		// ⊥ < a < b < ⊤, ⊥ < c < ⊤
+		let r = RelationVec::from_predicate(&['a', 'b', 'c', 'o', 't'], |(&x, &y)| {
+			x == y || x == 'o' || y == 't' || (x, y) == ('a', 'b')
+		});
+		let l = Lattice::new(&r).unwrap();
+		let w = l.check_modular().unwrap_err();
+		assert_sublattice_witness(&l, &w);
+		assert_sublattice_witness(&l, &l.check_distributive().unwrap_err());
+		assert!(l.check_complemented().is_ok());
+		assert_eq!(
+			l.check_relatively_complemented().unwrap_err(),
+			LatticeWitness::NoRelativeComplement(SetElement::from(&'b'), (SetElement::from(&'a'), SetElement::from(&'t'))),
+		);
+		assert!(!r.is_modular());
+		assert!(!r.is_boolean());
+	}
+	#[test]
+	fn m3() {
+		// ⊥ < a, b, c < ⊤
+		let r = RelationVec::from_predicate(&['a', 'b', 'c', 'o', 't'], |(&x, &y)| {
+			x == y || x == 'o' || y == 't'
+		});
+		let l = Lattice::new(&r).unwrap();
+		assert!(l.check_modular().is_ok());
+		let w = l.check_distributive().unwrap_err();
+		assert_eq!(w.to_string(), "M3 {o a b c t}");
+		assert_sublattice_witness(&l, &w);
+		assert!(r.is_complemented());
+		assert!(r.is_relatively_complemented());
+		assert!(!r.is_distributive());
+		assert!(!r.is_boolean());
+	}
+	#[test]
+	fn boolean() {
+		let r = closure_system(&[0b01111, 0b10011, 0b11100]);
+		assert!(r.is_boolean());
+		assert!(r.is_relatively_complemented());
+		// divisors of 12 are distributive, but 2 has no complement
+		let r = divisibility(&[1, 12, 2, 3, 4, 6]);
+		assert!(r.is_distributive());
+		assert_eq!(
+			r.lattice().unwrap().check_boolean().unwrap_err(),
+			LatticeWitness::NoComplement(e(2)),
+		);
+		// not a lattice
+		let r = divisibility(&[1, 2, 3]);
+		assert!(!r.is_modular());
+		assert!(!r.is_complemented());
+	}
+
+	proptest! {
+		#[test]
+		fn classification(family in vec(0..32u8, 0..6)) {
+			let r = closure_system(&family);
+			let l = Lattice::new(&r).unwrap();
+			let (j, m) = (|x, y| l.join_at(x, y), |x, y| l.meet_at(x, y));
+			let n = r.get_domain().0.cardinality();
+			let triples = || (0..n).flat_map(|a| (0..n).flat_map(move |b| (0..n).map(move |c| (a, b, c))));
+
+			let distributive = triples().all(|(a, b, c)| m(a, j(b, c)) == j(m(a, b), m(a, c)));
+			match l.check_distributive() {
+				Ok(()) => assert!(distributive),
+				Err(w) => { assert!(!distributive); assert_sublattice_witness(&l, &w); }
+			}
+			let modular = triples()
+				.filter(|&(a, _, c)| r.eval_at(a, c))
+				.all(|(a, b, c)| j(a, m(b, c)) == m(j(a, b), c));
+			match l.check_modular() {
+				Ok(()) => assert!(modular),
+				Err(w) => { assert!(!modular); assert_sublattice_witness(&l, &w); }
+			}
+			assert_eq!(r.is_boolean(), r.is_distributive() && r.is_complemented());
+			if r.is_relatively_complemented() {
+				assert!(r.is_complemented());
+			}
+		}
+	}
+
+	#[test]
+	fn dedekind_macneille_antichain() {
+		let r = RelationVec::from_predicate(&['a', 'b'], |(x, y)| x == y);
+		assert!(!r.is_lattice());
+		let (completion, embedding) = RelationVec::completion_dedekind_macneille(&r).unwrap();
+		assert!(completion.is_lattice());
+		let cuts: Vec<String> = completion.get_domain().0.iter().map(|c| c.to_string()).collect();
+		assert_eq!(cuts, vec!["{}", "{a}", "{a b}", "{b}"]);
+		let a: Set = ['a'].iter().collect();
+		assert_eq!(embedding.image(&a), std::iter::once(SetElement::from(a)).collect());
+	}
+	#[test]
+	fn dedekind_macneille_lattice() {
+		// 4 and 6 gain a supremum
+		let r = divisibility(&[1, 2, 3, 4, 6]);
+		let (completion, _) = RelationVec::completion_dedekind_macneille(&r).unwrap();
+		assert_eq!(completion.get_domain().0.cardinality(), 6);
+		// a lattice is its own completion
+		let r = divisibility(&[1, 2, 3, 6]);
+		let (completion, embedding) = RelationVec::completion_dedekind_macneille(&r).unwrap();
+		assert_eq!(completion.get_domain().0.cardinality(), 4);
+		assert!(embedding.is_bijective());
+		let r = RelationVec::from_predicate(&[1, 2, 3], |(x, y)| x < y);
+		assert_eq!(RelationVec::completion_dedekind_macneille(&r), None);
+	}
+
+	/// A partial order on `{0, ..., n-1}` contained in `≤`.
+	fn partial_order() -> impl Strategy<Value = RelationVec> {
+		(1..8usize)
+			.prop_flat_map(|n| vec(any::<bool>(), n * n))
+			.prop_map(|t| {
+				let n = (t.len() as f64).sqrt() as usize;
+				let r = RelationVec::new(
+					((0..n as i32).collect(), (0..n as i32).collect()),
+					(0..n * n).map(|i| t[i] && i / n <= i % n).collect(),
+				);
+				RelationVec::from(RelationVec::closure_reflexive_transitive(&r))
+			})
+	}
+
+	proptest! {
+		#[test]
+		fn dedekind_macneille(r in partial_order()) {
+			let (completion, embedding) = RelationVec::completion_dedekind_macneille(&r).unwrap();
+			assert!(completion.is_lattice());
+			assert!(embedding.is_function() && embedding.is_injective());
+			// the embedding is an order embedding
+			for x in r.get_domain().0.iter() {
+				for y in r.get_domain().0.iter() {
+					let ex = embedding.image(&std::iter::once(x.clone()).collect());
+					let ey = embedding.image(&std::iter::once(y.clone()).collect());
+					let (ex, ey) = (ex.iter().next().unwrap(), ey.iter().next().unwrap());
+					assert_eq!(r.eval(x, y), completion.eval(ex, ey));
+				}
+			}
+			if r.is_lattice() {
+				assert!(embedding.is_bijective());
+			}
+		}
+	}
+
+	#[test]
+	fn chain() {
+		let r = RelationVec::from_predicate(&[1, 2, 3, 4, 5], |(x, y)| x <= y);
+		let l = Lattice::new(&r).unwrap();
+		for x in r.get_domain().0.iter() {
+			for y in r.get_domain().0.iter() {
+				assert_eq!(l.join(x, y), std::cmp::max(x, y));
+				assert_eq!(l.meet(x, y), std::cmp::min(x, y));
+			}
+		}
+	}
+}
@@ -0,0 +1,286 @@
+//! Linear extensions of a partial order.
+//!
+//! A linear extension of a relation `R` is a total ordering `x₁, x₂, ..., xₙ`
+//! of its domain with `xᵢRxⱼ ⇒ i ≤ j`, i.e. a topological sort.
+//! Loops are ignored, so linear extensions exist for partial orders and
+//! all other acyclic relations.
+
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+use std::vec::Vec;
+
+use crate::set::SetElement;
+use crate::relation::Endorelation;
+
+/// A cycle `x₁ R x₂ R ... R xₙ R x₁` of distinct elements, which prevents a
+/// relation from having a linear extension.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle(pub Vec<SetElement>);
+
+impl fmt::Display for Cycle {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "cycle")?;
+		for x in self.0.iter().chain(self.0.first()) {
+			write!(f, " {}", x)?;
+		}
+		Ok(())
+	}
+}
+
+impl error::Error for Cycle {}
+
+/// Sort the indices of the domain of `r` topologically, preferring the
+/// smallest index whenever there is a choice.
+/// Return the indices of a cycle if there is no topological order.
+pub(crate) fn topological_sort<R: Endorelation>(r: &R) -> Result<Vec<usize>, Vec<usize>> {
+	debug_assert!(r.is_homogeneous());
+	let mut indegree: Vec<usize> = r.iys()
+		.map(|iy| r.ixs().filter(|&ix| ix != iy && r.eval_at(ix, iy)).count())
+		.collect();
+	let mut placed = vec![false; indegree.len()];
+	let mut order = Vec::with_capacity(indegree.len());
+	while let Some(ix) = r.ixs().find(|&i| !placed[i] && indegree[i] == 0) {
+		placed[ix] = true;
+		order.push(ix);
+		for iy in r.iys().filter(|&iy| iy != ix && r.eval_at(ix, iy)) {
+			indegree[iy] -= 1;
+		}
+	}
+	if order.len() == placed.len() {
+		return Ok(order);
+	}
+	// every remaining element has a remaining predecessor,
+	// so walking backwards eventually repeats an element
+	let mut path = vec![r.ixs().find(|&i| !placed[i]).unwrap()];
+	loop {
+		let iy = *path.last().unwrap();
+		let ix = r.ixs().find(|&ix| !placed[ix] && ix != iy && r.eval_at(ix, iy)).unwrap();
+		if let Some(start) = path.iter().position(|&i| i == ix) {
+			let mut cycle = path.split_off(start);
+			cycle.reverse();
+			return Err(cycle);
+		}
+		path.push(ix);
+	}
+}
+
+/// Map the indices of a cycle to its elements.
+pub(crate) fn cycle<R: Endorelation>(r: &R, indices: Vec<usize>) -> Cycle {
+	let elements: Vec<&SetElement> = r.get_domain().0.iter().collect();
+	Cycle(indices.into_iter().map(|i| elements[i].clone()).collect())
+}
+
+/// An iterator over all linear extensions of a relation in lexicographic
+/// order of the indices of their elements.
+///
+/// Created by [`Endorelation::linear_extensions`].
+#[derive(Clone, Debug)]
+pub struct LinearExtensions<'a, R: Endorelation> {
+	r: &'a R,
+	elements: Vec<&'a SetElement>,
+	indegree: Vec<usize>,
+	placed: Vec<bool>,
+	order: Vec<usize>,
+	started: bool,
+	done: bool,
+}
+
+impl<'a, R: Endorelation> LinearExtensions<'a, R> {
+	/// Create the iterator over all linear extensions of `r`
+	/// or return a cycle of `r`.
+	pub fn new(r: &'a R) -> Result<Self, Cycle> {
+		topological_sort(r).map_err(|c| cycle(r, c))?;
+		let indegree = r.iys()
+			.map(|iy| r.ixs().filter(|&ix| ix != iy && r.eval_at(ix, iy)).count())
+			.collect();
+		Ok(LinearExtensions {
+			r,
+			elements: r.get_domain().0.iter().collect(),
+			indegree,
+			placed: vec![false; r.get_domain().0.cardinality()],
+			order: Vec::new(),
+			started: false,
+			done: false,
+		})
+	}
+
+	fn place(&mut self, ix: usize) {
+		self.placed[ix] = true;
+		self.order.push(ix);
+		let r = self.r;
+		for iy in r.iys().filter(|&iy| iy != ix && r.eval_at(ix, iy)) {
+			self.indegree[iy] -= 1;
+		}
+	}
+	fn unplace(&mut self) -> Option<usize> {
+		let ix = self.order.pop()?;
+		self.placed[ix] = false;
+		let r = self.r;
+		for iy in r.iys().filter(|&iy| iy != ix && r.eval_at(ix, iy)) {
+			self.indegree[iy] += 1;
+		}
+		Some(ix)
+	}
+	/// The first index from `start` that can be placed next.
+	fn candidate(&self, start: usize) -> Option<usize> {
+		(start..self.placed.len()).find(|&i| !self.placed[i] && self.indegree[i] == 0)
+	}
+	/// Complete the current order with the smallest candidates,
+	/// which always succeeds for acyclic relations.
+	fn fill(&mut self) -> Vec<SetElement> {
+		while let Some(ix) = self.candidate(0) {
+			self.place(ix);
+		}
+		debug_assert_eq!(self.order.len(), self.placed.len());
+		self.order.iter().map(|&i| self.elements[i].clone()).collect()
+	}
+}
+
+impl<R: Endorelation> Iterator for LinearExtensions<'_, R> {
+	type Item = Vec<SetElement>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.done { return None; }
+		if !self.started {
+			self.started = true;
+			return Some(self.fill());
+		}
+		// replace the last element that has a greater alternative
+		while let Some(ix) = self.unplace() {
+			if let Some(iy) = self.candidate(ix + 1) {
+				self.place(iy);
+				return Some(self.fill());
+			}
+		}
+		self.done = true;
+		None
+	}
+}
+
+/// Count the linear extensions of the acyclic relation `r`.
+///
+/// Counts the ways to extend every down-set once, which takes time and
+/// memory proportional to the number of down-sets.
+pub(crate) fn count<R: Endorelation>(r: &R) -> u128 {
+	let n = r.get_domain().0.cardinality();
+	let words = n.div_ceil(64);
+	// the strict predecessors of every element
+	let predecessors: Vec<Vec<u64>> = r.iys()
+		.map(|iy| {
+			let mut p = vec![0u64; words];
+			for ix in r.ixs().filter(|&ix| ix != iy && r.eval_at(ix, iy)) {
+				p[ix / 64] |= 1 << (ix % 64);
+			}
+			p
+		})
+		.collect();
+	let mut memo = HashMap::new();
+	count_from(&predecessors, &mut vec![0u64; words], n, &mut memo)
+}
+
+/// Count the linear extensions of the elements not in the down-set `placed`.
+fn count_from(
+	predecessors: &[Vec<u64>],
+	placed: &mut Vec<u64>,
+	remaining: usize,
+	memo: &mut HashMap<Vec<u64>, u128>,
+) -> u128 {
+	if remaining <= 1 { return 1; }
+	if let Some(&c) = memo.get(placed) { return c; }
+	let mut c = 0;
+	for (iy, p) in predecessors.iter().enumerate() {
+		let (w, bit) = (iy / 64, 1 << (iy % 64));
+		let available = placed[w] & bit == 0
+			&& p.iter().zip(placed.iter()).all(|(p, s)| p & !s == 0);
+		if available {
+			placed[w] |= bit;
+			c += count_from(predecessors, placed, remaining - 1, memo);
+			placed[w] &= !bit;
+		}
+	}
+	memo.insert(placed.clone(), c);
+	c
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::relation::{Relation, RelationVec};
+	use crate::relation::relation_vec::tests::{relation_arbitrary, domain_homogeneous};
+	use crate::relation::relation::tests::elements;
+	use proptest::prelude::*;
+
+	#[test]
+	fn linear_extension() {
+		// a < c, b < c, c < d
+		let r = RelationVec::from_predicate(&['a', 'b', 'c', 'd'], |(&x, &y)| {
+			x == y || y == 'd' || (y == 'c' && x != 'd')
+		});
+		assert!(r.is_partial_order());
+		assert_eq!(r.linear_extension(), Ok(elements("abcd")));
+		let all: Vec<Vec<SetElement>> = r.linear_extensions().unwrap().collect();
+		assert_eq!(all, vec![elements("abcd"), elements("bacd")]);
+		assert_eq!(r.count_linear_extensions(), Ok(2));
+	}
+	#[test]
+	fn antichain() {
+		let r = RelationVec::from_predicate(&['a', 'b', 'c'], |(x, y)| x == y);
+		let all: Vec<Vec<SetElement>> = r.linear_extensions().unwrap().collect();
+		assert_eq!(all.len(), 6);
+		assert_eq!(all[0], elements("abc"));
+		assert_eq!(all[5], elements("cba"));
+		let n12: Vec<u8> = (0..12).collect();
+		let r = RelationVec::from_predicate(&n12, |(x, y)| x == y);
+		assert_eq!(r.count_linear_extensions(), Ok((1..=12).product()));
+	}
+	#[test]
+	fn empty() {
+		let r = RelationVec::from_predicate(&[0u8; 0], |_| true);
+		assert_eq!(r.linear_extension(), Ok(vec![]));
+		assert_eq!(r.linear_extensions().unwrap().count(), 1);
+		assert_eq!(r.count_linear_extensions(), Ok(1));
+	}
+	#[test]
+	fn cycle() {
+		// a → b → c → b, d
+		let r = RelationVec::from_predicate(&['a', 'b', 'c', 'd'], |(&x, &y)| {
+			[('a', 'b'), ('b', 'c'), ('c', 'b')].contains(&(x, y))
+		});
+		let c = Cycle(elements("cb"));
+		assert_eq!(r.linear_extension(), Err(c.clone()));
+		assert_eq!(r.linear_extensions().unwrap_err(), c);
+		assert_eq!(r.count_linear_extensions(), Err(c));
+		assert_eq!(Cycle(elements("bc")).to_string(), "cycle b c b");
+	}
+
+	proptest! {
+		#[test]
+		fn linear_extensions(r in relation_arbitrary(domain_homogeneous())) {
+			match r.linear_extension() {
+				Err(Cycle(c)) => {
+					// consecutive elements are related
+					for (x, y) in c.iter().zip(c.iter().cycle().skip(1)) {
+						assert!(x != y && r.eval(x, y));
+					}
+				}
+				Ok(order) => {
+					let position = |x: &SetElement| order.iter().position(|e| e == x).unwrap();
+					for x in r.get_domain().0.iter() {
+						for y in r.get_domain().1.iter().filter(|&y| r.eval(x, y)) {
+							assert!(position(x) <= position(y));
+						}
+					}
+					// enumerate only small numbers of extensions
+					let count = r.count_linear_extensions().unwrap();
+					if count <= 1000 {
+						let all: Vec<_> = r.linear_extensions().unwrap().collect();
+						assert_eq!(all.len() as u128, count);
+						assert_eq!(all[0], order);
+						assert!(all.windows(2).all(|w| w[0] != w[1]));
+					}
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,154 @@
+//! Power sets and the membership relation.
+//!
+//! The membership relation `ε ⊆ X × 𝒫(X)` relates every element to all
+//! subsets containing it, i.e. `x ε A ⇔ x ∈ A`. Together with the residuals
+//! it allows to express quantification relationally, e.g. the inclusion
+//! order on `𝒫(X)` is the left residual `ε \ ε`.
+//!
+//! The relations in this module are lazy, none of the `n·2ⁿ` cells of `ε`
+//! are stored. Their domain `𝒫(X)` is built eagerly, though, and its `2ⁿ`
+//! subsets hold `n·2ⁿ⁻¹` elements in total, so `X` can have at most
+//! [`MAX_POWERSET`] elements.
+
+use std::vec::Vec;
+
+use crate::set::{self, Set, SetElement, MAX_POWERSET};
+use crate::relation::{Relation, Endorelation};
+
+/// The membership relation `ε ⊆ X × 𝒫(X)`.
+#[derive(Clone, Debug)]
+pub struct Epsilon {
+	set: Set,
+	powerset: Set,
+	/// The elements of the subset at every index, as a bitmask of indices into `X`.
+	masks: Vec<u64>,
+}
+
+/// Create the membership relation `ε ⊆ X × 𝒫(X)` of `x`.
+pub fn epsilon(x: &Set) -> Epsilon {
+	Epsilon::new(x)
+}
+
+impl Epsilon {
+	/// Create the membership relation `ε ⊆ X × 𝒫(X)` of `x`.
+	///
+	/// Panics if `x` has more than [`MAX_POWERSET`] elements,
+	/// see [`Set::powerset`].
+	pub fn new(x: &Set) -> Self {
+		let elements: Vec<&SetElement> = x.iter().collect();
+		assert!(elements.len() <= MAX_POWERSET, "power set too large");
+		// sort the subsets like the power set does
+		let mut subsets: Vec<(Set, u64)> = (0..1u64 << elements.len())
+			.map(|mask| (set::subset(&elements, mask), mask))
+			.collect();
+		subsets.sort();
+		let masks = subsets.iter().map(|&(_, mask)| mask).collect();
+		Epsilon {
+			set: x.clone(),
+			powerset: subsets.into_iter().map(|(s, _)| s).collect(),
+			masks,
+		}
+	}
+	/// The set `X`.
+	pub fn get_set(&self) -> &Set {
+		&self.set
+	}
+	/// The power set `𝒫(X)`.
+	pub fn get_powerset(&self) -> &Set {
+		&self.powerset
+	}
+
+	/// The inclusion order `⊆` on `𝒫(X)`.
+	pub fn inclusion(&self) -> Inclusion<'_> {
+		Inclusion { epsilon: self, strict: false }
+	}
+	/// The strict inclusion order `⊂` on `𝒫(X)`.
+	pub fn inclusion_strict(&self) -> Inclusion<'_> {
+		Inclusion { epsilon: self, strict: true }
+	}
+}
+
+impl Relation for Epsilon {
+	fn get_domain(&self) -> (&Set, &Set) {
+		(&self.set, &self.powerset)
+	}
+	fn eval_at(&self, ix: usize, is: usize) -> bool {
+		self.masks[is] >> ix & 1 == 1
+	}
+}
+
+/// The (strict) inclusion order on the power set of an [`Epsilon`].
+#[derive(Clone, Debug)]
+pub struct Inclusion<'a> {
+	epsilon: &'a Epsilon,
+	strict: bool,
+}
+
+impl Relation for Inclusion<'_> {
+	fn get_domain(&self) -> (&Set, &Set) {
+		(&self.epsilon.powerset, &self.epsilon.powerset)
+	}
+	fn eval_at(&self, is: usize, it: usize) -> bool {
+		let (s, t) = (self.epsilon.masks[is], self.epsilon.masks[it]);
+		s & !t == 0 && !(self.strict && s == t)
+	}
+}
+
+impl Endorelation for Inclusion<'_> {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::relation::{self, RelationVec};
+	use crate::relation::relation::tests::set;
+	use proptest::prelude::*;
+
+	#[test]
+	fn powerset() {
+		assert_eq!(set("").powerset().to_string(), "{{}}");
+		assert_eq!(set("ab").powerset().to_string(), "{{} {a} {a b} {b}}");
+		assert_eq!(set("abcde").powerset().cardinality(), 32);
+	}
+	#[test]
+	#[should_panic]
+	fn powerset_too_large() {
+		let x: Set = (0..MAX_POWERSET as i32 + 1).collect();
+		epsilon(&x);
+	}
+	#[test]
+	fn membership() {
+		let e = epsilon(&set("abc"));
+		assert_eq!(e.get_powerset(), &set("abc").powerset());
+		let (a, b) = (SetElement::from(&'a'), SetElement::from(&'b'));
+		let (ab, bc) = (SetElement::from(set("ab")), SetElement::from(set("bc")));
+		assert!(e.eval(&a, &ab));
+		assert!(!e.eval(&a, &bc));
+		assert!(e.eval(&b, &bc));
+		assert!(e.inclusion().eval(&ab, &SetElement::from(set("abc"))));
+		assert!(!e.inclusion().eval(&ab, &bc));
+		assert!(e.inclusion().eval(&ab, &ab));
+		assert!(!e.inclusion_strict().eval(&ab, &ab));
+	}
+
+	proptest! {
+		#[test]
+		fn epsilon_properties(x in prop::collection::btree_set(0..100i32, 0..6)) {
+			let x: Set = x.into_iter().collect();
+			let e = epsilon(&x);
+			// x ε A ⇔ x ∈ A
+			for s in e.get_powerset().iter() {
+				let SetElement::Set(s) = s else { panic!("not a set: {}", s) };
+				assert!(x.iter().all(|x| e.eval(x, &SetElement::from(s.clone())) == s.contains(x)));
+			}
+			// extensionality: syq(ε, ε) = I
+			let id = RelationVec::identity((e.get_powerset(), e.get_powerset()));
+			assert!(relation::eq(&RelationVec::symmetric_quotient(&e, &e), &id));
+			// ⊆ = ε \ ε is a boolean lattice and ⊂ = ⊆ ∖ I
+			let inclusion = e.inclusion();
+			assert!(relation::eq(&inclusion, &RelationVec::left_residual(&e, &e)));
+			assert!(inclusion.is_boolean());
+			let not_id = RelationVec::complement(&id);
+			assert!(relation::eq(&e.inclusion_strict(), &RelationVec::intersection(&inclusion, &not_id)));
+		}
+	}
+}
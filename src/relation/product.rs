@@ -0,0 +1,313 @@
+//! Direct products and direct sums of sets.
+//!
+//! This module contains the [`DirectProduct`] `X × Y` with its projections
+//! `π`, `ρ` and the fork `[R, S]`, as well as the [`DirectSum`] `X + Y` with
+//! its injections `ι`, `κ` and the join `[R; S]`.
+//! All of them are lazy [`Relation`]s and can be combined with the other
+//! operations, e.g. [`Relation::concatenation`].
+//!
+//! An ordered pair `(x, y)` is a [`SetElement::Tuple`], the elements of
+//! `X + Y` are the pairs `(0, x)` and `(1, y)`.
+
+use crate::cow::LCow;
+use crate::set::{Set, SetElement};
+use crate::relation::Relation;
+
+/// The ordered pair `(x, y)` as a [`SetElement::Tuple`], displayed as `(x, y)`.
+pub fn pair(x: &SetElement, y: &SetElement) -> SetElement {
+	SetElement::from((x.clone(), y.clone()))
+}
+
+/// The direct product `X × Y` of two sets.
+///
+/// The pairs are ordered lexicographically, so the pair `(x, y)` at index
+/// `ix · |Y| + iy` consists of the elements at the indices `ix` and `iy`.
+#[derive(Clone, Debug)]
+pub struct DirectProduct {
+	set: Set,
+	factors: (Set, Set),
+}
+
+impl DirectProduct {
+	/// Create the direct product of `x` and `y`.
+	pub fn new(x: &Set, y: &Set) -> Self {
+		DirectProduct {
+			set: x.cartesian_product(y),
+			factors: (x.clone(), y.clone()),
+		}
+	}
+	/// The set `X × Y` of all pairs `(x, y)`.
+	pub fn get_set(&self) -> &Set {
+		&self.set
+	}
+	/// The factors `X` and `Y`.
+	pub fn get_factors(&self) -> (&Set, &Set) {
+		(&self.factors.0, &self.factors.1)
+	}
+	/// The indices `(ix, iy)` of the pair at index `i`.
+	pub fn pair_at(&self, i: usize) -> (usize, usize) {
+		let n = self.factors.1.cardinality();
+		(i / n, i % n)
+	}
+
+	/// The projection `π ⊆ (X × Y) × X` where `(x, y) π x`.
+	pub fn pi(&self) -> Projection<'_> {
+		Projection { product: self, first: true }
+	}
+	/// The projection `ρ ⊆ (X × Y) × Y` where `(x, y) ρ y`.
+	pub fn rho(&self) -> Projection<'_> {
+		Projection { product: self, first: false }
+	}
+	/// The fork `[R, S] ⊆ Z × (X × Y)` of `R ⊆ Z × X` and `S ⊆ Z × Y`,
+	/// defined as `[R, S] = R ; πᵀ ∩ S ; ρᵀ`, i.e. `z [R, S] (x, y) ⇔ zRx ∧ zSy`.
+	pub fn fork<'a, P, Q>(&'a self, p: &'a P, q: &'a Q) -> Fork<'a, P, Q>
+	where P: Relation,
+	      Q: Relation,
+	{
+		let (p, q): (LCow<'a, P>, LCow<'a, Q>) = (p.into(), q.into());
+		debug_assert_eq!(p.get_domain().0, q.get_domain().0);
+		debug_assert_eq!(p.get_domain().1, &self.factors.0);
+		debug_assert_eq!(q.get_domain().1, &self.factors.1);
+		Fork { product: self, p, q }
+	}
+}
+
+/// A projection of a [`DirectProduct`] onto one of its factors.
+#[derive(Clone, Debug)]
+pub struct Projection<'a> {
+	product: &'a DirectProduct,
+	first: bool,
+}
+
+impl Relation for Projection<'_> {
+	fn get_domain(&self) -> (&Set, &Set) {
+		let factor = if self.first { &self.product.factors.0 } else { &self.product.factors.1 };
+		(&self.product.set, factor)
+	}
+	fn eval_at(&self, ip: usize, i: usize) -> bool {
+		let (ix, iy) = self.product.pair_at(ip);
+		i == if self.first { ix } else { iy }
+	}
+}
+
+/// The fork `[R, S]` of two relations into a [`DirectProduct`].
+#[derive(Clone, Debug)]
+pub struct Fork<'a, P: Relation, Q: Relation> {
+	product: &'a DirectProduct,
+	p: LCow<'a, P>,
+	q: LCow<'a, Q>,
+}
+
+impl<P: Relation, Q: Relation> Relation for Fork<'_, P, Q> {
+	fn get_domain(&self) -> (&Set, &Set) {
+		(self.p.get_domain().0, &self.product.set)
+	}
+	fn eval_at(&self, iz: usize, ip: usize) -> bool {
+		let (ix, iy) = self.product.pair_at(ip);
+		self.p.eval_at(iz, ix) && self.q.eval_at(iz, iy)
+	}
+}
+
+/// The direct sum (disjoint union) `X + Y` of two sets.
+///
+/// The pairs `(0, x)` precede the pairs `(1, y)`, so the element at index
+/// `i < |X|` is `(0, x)` with the `x` at index `i`.
+#[derive(Clone, Debug)]
+pub struct DirectSum {
+	set: Set,
+	summands: (Set, Set),
+}
+
+impl DirectSum {
+	/// Create the direct sum of `x` and `y`.
+	pub fn new(x: &Set, y: &Set) -> Self {
+		let zero: Set = std::iter::once(0).collect();
+		let one: Set = std::iter::once(1).collect();
+		DirectSum {
+			set: zero.cartesian_product(x).union(&one.cartesian_product(y)).cloned().collect(),
+			summands: (x.clone(), y.clone()),
+		}
+	}
+	/// The set `X + Y` of all pairs `(0, x)` and `(1, y)`.
+	pub fn get_set(&self) -> &Set {
+		&self.set
+	}
+	/// The summands `X` and `Y`.
+	pub fn get_summands(&self) -> (&Set, &Set) {
+		(&self.summands.0, &self.summands.1)
+	}
+	/// The summand (`0` or `1`) and the index therein of the pair at index `i`.
+	pub fn tag_at(&self, i: usize) -> (usize, usize) {
+		let n = self.summands.0.cardinality();
+		if i < n { (0, i) } else { (1, i - n) }
+	}
+
+	/// The injection `ι ⊆ X × (X + Y)` where `x ι (0, x)`.
+	pub fn iota(&self) -> Injection<'_> {
+		Injection { sum: self, summand: 0 }
+	}
+	/// The injection `κ ⊆ Y × (X + Y)` where `y κ (1, y)`.
+	pub fn kappa(&self) -> Injection<'_> {
+		Injection { sum: self, summand: 1 }
+	}
+	/// The join `[R; S] ⊆ (X + Y) × Z` of `R ⊆ X × Z` and `S ⊆ Y × Z`,
+	/// defined as `[R; S] = ιᵀ ; R ∪ κᵀ ; S`, i.e. `(0, x) [R; S] z ⇔ xRz`
+	/// and `(1, y) [R; S] z ⇔ ySz`.
+	pub fn join<'a, P, Q>(&'a self, p: &'a P, q: &'a Q) -> Join<'a, P, Q>
+	where P: Relation,
+	      Q: Relation,
+	{
+		let (p, q): (LCow<'a, P>, LCow<'a, Q>) = (p.into(), q.into());
+		debug_assert_eq!(p.get_domain().1, q.get_domain().1);
+		debug_assert_eq!(p.get_domain().0, &self.summands.0);
+		debug_assert_eq!(q.get_domain().0, &self.summands.1);
+		Join { sum: self, p, q }
+	}
+}
+
+/// An injection of a summand into a [`DirectSum`].
+#[derive(Clone, Debug)]
+pub struct Injection<'a> {
+	sum: &'a DirectSum,
+	summand: usize,
+}
+
+impl Relation for Injection<'_> {
+	fn get_domain(&self) -> (&Set, &Set) {
+		let summand = if self.summand == 0 { &self.sum.summands.0 } else { &self.sum.summands.1 };
+		(summand, &self.sum.set)
+	}
+	fn eval_at(&self, i: usize, is: usize) -> bool {
+		self.sum.tag_at(is) == (self.summand, i)
+	}
+}
+
+/// The join `[R; S]` of two relations out of a [`DirectSum`].
+#[derive(Clone, Debug)]
+pub struct Join<'a, P: Relation, Q: Relation> {
+	sum: &'a DirectSum,
+	p: LCow<'a, P>,
+	q: LCow<'a, Q>,
+}
+
+impl<P: Relation, Q: Relation> Relation for Join<'_, P, Q> {
+	fn get_domain(&self) -> (&Set, &Set) {
+		(&self.sum.set, self.p.get_domain().1)
+	}
+	fn eval_at(&self, is: usize, iz: usize) -> bool {
+		match self.sum.tag_at(is) {
+			(0, ix) => self.p.eval_at(ix, iz),
+			(_, iy) => self.q.eval_at(iy, iz),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::relation::{self, Endorelation, RelationVec};
+	use crate::relation::relation_vec::tests::relation_for_domain;
+	use crate::relation::relation::tests::set;
+	use proptest::prelude::*;
+
+	#[test]
+	fn direct_product() {
+		let (x, y) = (set("ab"), set("uvw"));
+		let product = DirectProduct::new(&x, &y);
+		assert_eq!(product.get_set().cardinality(), 6);
+		let (a, w) = (SetElement::from(&'a'), SetElement::from(&'w'));
+		assert_eq!(pair(&a, &w).to_string(), "(a, w)");
+		assert!(product.get_set().contains(&pair(&a, &w)));
+		assert!(product.pi().eval(&pair(&a, &w), &a));
+		assert!(product.rho().eval(&pair(&a, &w), &w));
+		assert!(!product.rho().eval(&pair(&a, &w), &SetElement::from(&'u')));
+		let i = product.get_set().iter().position(|p| p == &pair(&a, &w)).unwrap();
+		assert_eq!(product.pair_at(i), (0, 2));
+		assert_eq!(set("ab").cartesian_product(&set("xy")).to_string(), "{(a, x) (a, y) (b, x) (b, y)}");
+		assert_eq!(set("a").cartesian_product(&Set::new()), Set::new());
+		// the index of every pair agrees with the indices of its components
+		let (xs, ys): (Vec<&SetElement>, Vec<&SetElement>) = (x.iter().collect(), y.iter().collect());
+		for (i, p) in product.get_set().iter().enumerate() {
+			let (ix, iy) = product.pair_at(i);
+			assert_eq!(p, &pair(xs[ix], ys[iy]));
+		}
+	}
+	#[test]
+	fn direct_sum() {
+		let (x, y) = (set("ab"), set("ab"));
+		let sum = DirectSum::new(&x, &y);
+		assert_eq!(sum.get_set().cardinality(), 4);
+		let a = SetElement::from(&'a');
+		assert!(sum.iota().eval(&a, &pair(&SetElement::from(0), &a)));
+		assert!(!sum.kappa().eval(&a, &pair(&SetElement::from(0), &a)));
+		assert!(sum.kappa().eval(&a, &pair(&SetElement::from(1), &a)));
+		assert_eq!(sum.get_set().to_string(), "{(0, a) (0, b) (1, a) (1, b)}");
+		assert_eq!(sum.tag_at(2), (1, 0));
+	}
+
+	prop_compose! {
+		/// Relations `r ⊆ Z × X` and `s ⊆ Z × Z` over small sets.
+		fn two_rels() (z in 1..6i32, x in 1..6u8) (
+			r in relation_for_domain(((0..z).collect(), (0..x).map(|i| (b'a' + i) as char).collect::<Vec<_>>().iter().collect())),
+			s in relation_for_domain(((0..z).collect(), (0..z).collect())),
+		) -> (RelationVec, RelationVec) {
+			(r, s)
+		}
+	}
+
+	proptest! {
+		#[test]
+		fn projections((r, s) in two_rels()) {
+			// X × Z, where r ⊆ Z × X and s ⊆ Z × Z
+			let (z, x) = r.get_domain();
+			let product = DirectProduct::new(x, z);
+			let (pi, rho) = (product.pi(), product.rho());
+			assert!(pi.is_function() && pi.is_surjective());
+			assert!(rho.is_function() && rho.is_surjective());
+			// π ; πᵀ ∩ ρ ; ρᵀ = I
+			let id = RelationVec::identity((product.get_set(), product.get_set()));
+			let (pi_t, rho_t) = (RelationVec::converse(&pi), RelationVec::converse(&rho));
+			let pp = RelationVec::concatenation(&pi, &pi_t);
+			let rr = RelationVec::concatenation(&rho, &rho_t);
+			assert!(relation::eq(&RelationVec::intersection(&pp, &rr), &id));
+			// πᵀ ; ρ = U
+			let pr = RelationVec::concatenation(&pi_t, &rho);
+			assert!(relation::eq(&pr, &RelationVec::universal(pr.get_domain())));
+
+			// [R, S] ; π = R ∩ S ; U and [R, S] ; ρ = S ∩ R ; U
+			let fork = product.fork(&r, &s);
+			let u = RelationVec::universal((z, x));
+			let su = RelationVec::concatenation(&s, &u);
+			assert!(relation::eq(&RelationVec::concatenation(&fork, &pi), &RelationVec::intersection(&r, &su)));
+			assert!(relation::eq(&fork, &RelationVec::intersection(
+				&RelationVec::concatenation(&r, &pi_t),
+				&RelationVec::concatenation(&s, &rho_t),
+			)));
+		}
+		#[test]
+		fn injections((r, s) in two_rels()) {
+			// r ⊆ Z × X, s ⊆ Z × Z
+			let (z, x) = r.get_domain();
+			let (r, s) = (RelationVec::from_relation(&RelationVec::converse(&r)), s);
+			let sum = DirectSum::new(x, z);
+			let (iota, kappa) = (sum.iota(), sum.kappa());
+			assert!(iota.is_function() && iota.is_injective());
+			assert!(kappa.is_function() && kappa.is_injective());
+			// ι ; ιᵀ = I, ι ; κᵀ = ∅, ιᵀ ; ι ∪ κᵀ ; κ = I
+			let (iota_t, kappa_t) = (RelationVec::converse(&iota), RelationVec::converse(&kappa));
+			let ii = RelationVec::concatenation(&iota, &iota_t);
+			assert!(relation::eq(&ii, &RelationVec::identity((x, x))));
+			let ik = RelationVec::concatenation(&iota, &kappa_t);
+			assert!(relation::eq(&ik, &RelationVec::empty((x, z))));
+			let ii = RelationVec::concatenation(&iota_t, &iota);
+			let kk = RelationVec::concatenation(&kappa_t, &kappa);
+			let id = RelationVec::identity((sum.get_set(), sum.get_set()));
+			assert!(relation::eq(&RelationVec::union(&ii, &kk), &id));
+
+			// ι ; [R; S] = R and κ ; [R; S] = S
+			let join = sum.join(&r, &s);
+			assert!(relation::eq(&RelationVec::concatenation(&iota, &join), &r));
+			assert!(relation::eq(&RelationVec::concatenation(&kappa, &join), &s));
+		}
+	}
+}
@@ -7,6 +7,8 @@ use std;
 use crate::cow::LCow;
 
 use crate::set::{Set, SetElement};
+use crate::relation::{RelationMut, RelationBits};
+use crate::union_find::UnionFind;
 
 pub trait Relation : Clone {
 	fn get_domain(&self) -> (&Set, &Set);
@@ -110,6 +112,79 @@ pub trait Relation : Clone {
 	/// A relation is a function iff it is functional and left-total
 	fn is_function(&self) -> bool { self.is_functional() && self.is_lefttotal() }
 
+	/// Return `true` if the relation is difunctional.
+	/// A relation is difunctional iff `∀x,z ∈ X and w,y ∈ Y: xRy ∧ zRy ∧ zRw ⇒ xRw`
+	///
+	/// aka regular
+	fn is_difunctional(&self) -> bool {
+		for ix in self.ixs() {
+			for iy in self.iys() {
+				if !self.eval_at(ix, iy) { continue; }
+				for iz in (ix+1)..self.get_domain().0.cardinality() {
+					if !self.eval_at(iz, iy) { continue; }
+					for iw in self.iys() {
+						if self.eval_at(ix, iw) != self.eval_at(iz, iw) {
+							return false;
+						}
+					}
+				}
+			}
+		}
+		true
+	}
+	/// Return the decomposition of a difunctional relation into
+	/// disjoint rectangles `U × V` or `None` if the relation is not difunctional.
+	///
+	/// A relation is difunctional iff it is the union of rectangles
+	/// `U₁ × V₁ ∪ U₂ × V₂ ∪ ...` with pairwise disjoint `Uᵢ` and pairwise disjoint `Vᵢ`.
+	fn difunctional_decomposition(&self) -> Option<Vec<(Set, Set)>> {
+		let blocks = difunctional_blocks(self);
+		for (us, vs) in blocks.iter() {
+			for &ix in us.iter() {
+				if !vs.iter().all(|&iy| self.eval_at(ix, iy)) {
+					return None;
+				}
+			}
+		}
+		let xs: Vec<_> = self.get_domain().0.iter().collect();
+		let ys: Vec<_> = self.get_domain().1.iter().collect();
+		let rectangles = blocks.iter()
+			.map(|(us, vs)| (
+				us.iter().map(|&ix| xs[ix]).cloned().collect(),
+				vs.iter().map(|&iy| ys[iy]).cloned().collect(),
+			))
+			.collect();
+		Some(rectangles)
+	}
+
+	/// Return `true` if the relation is a Ferrers relation.
+	/// A relation is Ferrers iff `R ; ¬Rᵀ ; R ⊆ R`,
+	/// i.e. iff the sets of successors of all `x ∈ X` form a chain under inclusion.
+	///
+	/// aka biorder
+	fn is_ferrers(&self) -> bool {
+		let (rows, _) = self.staircase();
+		rows.windows(2).all(
+			|w| self.iys().all(|iy| !self.eval_at(w[1], iy) || self.eval_at(w[0], iy))
+		)
+	}
+	/// Return orderings of the row- and column-indices which arrange the
+	/// incidence matrix in staircase (echelon) form.
+	///
+	/// Rows are ordered by decreasing number of successors and columns by
+	/// decreasing number of predecessors.
+	/// The relation is Ferrers iff every row of the rearranged matrix is a
+	/// prefix of the row above it.
+	/// As the elements of a [`Set`] are always sorted, the orderings are
+	/// returned as permutations of the indices rather than as a new relation.
+	fn staircase(&self) -> (Vec<usize>, Vec<usize>) {
+		let mut rows: Vec<usize> = self.ixs().collect();
+		rows.sort_by_key(|&ix| std::cmp::Reverse(self.iys().filter(|&iy| self.eval_at(ix, iy)).count()));
+		let mut columns: Vec<usize> = self.iys().collect();
+		columns.sort_by_key(|&iy| std::cmp::Reverse(self.ixs().filter(|&ix| self.eval_at(ix, iy)).count()));
+		(rows, columns)
+	}
+
 	/// Return the source of the relation.
 	///
 	/// Given a relation `R` over the sets `X, Y`,
@@ -163,7 +238,7 @@ pub trait Relation : Clone {
 	/// Given a relation `R` over the sets `X, Y`,
 	/// the pre-image of the set `V \subseteq Y` under `R` is the set `{ x ∈ X: ∃y: y ∈ V ∧ xRy }`
 	fn preimage(&self, set: &Set) -> Set {
-		debug_assert!(set.is_subset(self.get_domain().0));
+		debug_assert!(set.is_subset(self.get_domain().1));
 		let v: Vec<((usize, usize), &SetElement)> = Set::intersection_enumerated(
 				self.get_domain().1,
 				set,
@@ -201,7 +276,7 @@ pub trait Relation : Clone {
 	/// Given a relation `R` over the sets `X, Y`,
 	/// the pre-image of the set `V \subseteq Y` under `R` is the set `{ x ∈ X: ∀y: y ∈ V → xRy }`
 	fn preimage_strict(&self, set: &Set) -> Set {
-		debug_assert!(set.is_subset(self.get_domain().0));
+		debug_assert!(set.is_subset(self.get_domain().1));
 		let v: Vec<((usize, usize), &SetElement)> = Set::intersection_enumerated(
 				self.get_domain().1,
 				set,
@@ -241,6 +316,38 @@ pub trait Relation : Clone {
 	{
 		Concatenation::new(p, q)
 	}
+	/// The left residual of two relations.
+	///
+	/// `R \ S ⊆ Y × Z` for `R ⊆ X × Y` and `S ⊆ X × Z`, defined as
+	/// `R \ S = { (y, z) | ∀x ∈ X: (x, y) ∈ R → (x, z) ∈ S } = ¬(Rᵀ ; ¬S)`.
+	/// It is the greatest relation `Q` with `R ; Q ⊆ S`.
+	fn left_residual<'a, P, Q>(p: &'a P, q: &'a Q) -> LeftResidual<'a, P, Q>
+	where P: Relation,
+	      Q: Relation,
+	{
+		LeftResidual::new(p, q)
+	}
+	/// The right residual of two relations.
+	///
+	/// `S / R ⊆ X × Y` for `S ⊆ X × Z` and `R ⊆ Y × Z`, defined as
+	/// `S / R = { (x, y) | ∀z ∈ Z: (y, z) ∈ R → (x, z) ∈ S } = ¬(¬S ; Rᵀ)`.
+	/// It is the greatest relation `Q` with `Q ; R ⊆ S`.
+	fn right_residual<'a, P, Q>(p: &'a P, q: &'a Q) -> RightResidual<'a, P, Q>
+	where P: Relation,
+	      Q: Relation,
+	{
+		RightResidual::new(p, q)
+	}
+	/// The symmetric quotient of two relations.
+	///
+	/// `syq(R, S) ⊆ Y × Z` for `R ⊆ X × Y` and `S ⊆ X × Z`, defined as
+	/// `syq(R, S) = { (y, z) | ∀x ∈ X: (x, y) ∈ R ↔ (x, z) ∈ S } = (R \ S) ∩ (S \ R)ᵀ`.
+	fn symmetric_quotient<'a, P, Q>(p: &'a P, q: &'a Q) -> SymmetricQuotient<'a, P, Q>
+	where P: Relation,
+	      Q: Relation,
+	{
+		SymmetricQuotient::new(p, q)
+	}
 	/// The converse of a relation.
 	///
 	/// `R^T = { (y, x) | (x, y) ∈ R }`
@@ -267,9 +374,125 @@ pub trait Relation : Clone {
 	{
 		Union::new(p, q)
 	}
+	/// The restriction of a relation to the sources in `u`.
+	///
+	/// `R|U ⊆ (U ∩ X) × Y` for `R ⊆ X × Y`, defined as `R|U = { (x, y) ∈ R | x ∈ U }`.
+	fn restrict_source<'a, R>(r: &'a R, u: &Set) -> Restriction<'a, R>
+	where R: Relation,
+	{
+		let v = r.get_domain().1.clone();
+		Restriction::new(r, u, &v)
+	}
+	/// The restriction of a relation to the targets in `v`.
+	///
+	/// `R|V ⊆ X × (V ∩ Y)` for `R ⊆ X × Y`, defined as `R|V = { (x, y) ∈ R | y ∈ V }`.
+	fn restrict_target<'a, R>(r: &'a R, v: &Set) -> Restriction<'a, R>
+	where R: Relation,
+	{
+		let u = r.get_domain().0.clone();
+		Restriction::new(r, &u, v)
+	}
+	/// The subrelation of a homogeneous relation induced by `s`.
+	///
+	/// `R|S ⊆ (S ∩ X) × (S ∩ X)` for `R ⊆ X × X`, defined as
+	/// `R|S = { (x, y) ∈ R | x ∈ S ∧ y ∈ S }`.
+	fn induced_subrelation<'a, R>(r: &'a R, s: &Set) -> Restriction<'a, R>
+	where R: Relation,
+	{
+		debug_assert!(r.is_homogeneous());
+		Restriction::new(r, s, s)
+	}
+	/// The Kronecker (tensor) product of two relations.
+	///
+	/// `R ⊗ S ⊆ (X × U) × (Y × V)` for `R ⊆ X × Y` and `S ⊆ U × V`, defined as
+	/// `R ⊗ S = { ((x, u), (y, v)) | (x, y) ∈ R ∧ (u, v) ∈ S }`.
+	/// This is also the parallel product `π ; R ; π'ᵀ ∩ ρ ; S ; ρ'ᵀ`,
+	/// see [`DirectProduct`](crate::relation::product::DirectProduct).
+	fn kronecker<'a, P, Q>(p: &'a P, q: &'a Q) -> Kronecker<'a, P, Q>
+	where P: Relation,
+	      Q: Relation,
+	{
+		Kronecker::new(p, q)
+	}
+
+	/// Upper Ferrers hull: a Ferrers relation containing `r`, calculated as
+	/// the fixpoint of `r ∪ r ; ¬rᵀ ; r`.
+	///
+	/// The result equals `r` iff `r` is Ferrers, but it is not a closure:
+	/// Ferrers relations are not closed under intersection, so in general
+	/// there is no unique smallest Ferrers relation containing `r`.
+	/// Both `{(a,1),(b,1),(b,2)}` and `{(a,1),(a,2),(b,2)}` are minimal
+	/// Ferrers relations containing `{(a,1),(b,2)}`, whose upper hull is the
+	/// universal relation, which contains both.
+	fn upper_ferrers_hull<R: Relation>(r: &R) -> RelationBits {
+		let mut bits = RelationBits::from_relation(r);
+		ferrers_fixpoint(&mut bits);
+		bits
+	}
+	/// Difunctional closure: the smallest difunctional relation containing `r`,
+	/// i.e. the fixpoint of `r ∪ r ; rᵀ ; r`.
+	///
+	/// See [`Relation::difunctional_decomposition`] for the rectangles
+	/// the result consists of.
+	fn closure_difunctional<R: Relation>(r: &R) -> RelationBits {
+		let mut bits = RelationBits::new((r.get_domain().0.clone(), r.get_domain().1.clone()));
+		for (us, vs) in difunctional_blocks(r) {
+			for &ix in us.iter() {
+				for &iy in vs.iter() {
+					bits.set_at(ix, iy, true);
+				}
+			}
+		}
+		bits
+	}
+}
+
+/// Replace `t` by `t ∪ t ; ¬tᵀ ; t` until it does not change anymore.
+fn ferrers_fixpoint(t: &mut RelationBits) {
+	// x (t;¬tᵀ;t) w ⇔ ∃z: succ(x) ⊈ succ(z) ∧ z t w
+	loop {
+		let mut next = t.clone();
+		for ix in t.ixs() {
+			for iz in t.ixs() {
+				let (x, z) = (t.row(ix), t.row(iz));
+				if x.iter().zip(z.iter()).any(|(&x, &z)| x & !z != 0) {
+					next.union_row_with(ix, z);
+				}
+			}
+		}
+		if next == *t { return; }
+		*t = next;
+	}
+}
+
+/// Partition the related elements of `r` into the connected components of the
+/// bipartite graph `X ⊎ Y` with the edges `r`.
+/// Returns the indices `(U, V)` of every component, ordered by their smallest element.
+fn difunctional_blocks<R: Relation>(r: &R) -> Vec<(Vec<usize>, Vec<usize>)> {
+	let n = r.get_domain().0.cardinality();
+	let mut uf = UnionFind::new(n + r.get_domain().1.cardinality());
+	for ix in r.ixs() {
+		for iy in r.iys() {
+			if r.eval_at(ix, iy) {
+				uf.union(ix, n + iy);
+			}
+		}
+	}
+	uf.sets().into_iter()
+		.map(|block| {
+			let (us, vs): (Vec<usize>, Vec<usize>) = block.into_iter().partition(|&i| i < n);
+			(us, vs.into_iter().map(|i| i - n).collect::<Vec<usize>>())
+		})
+		.filter(|(us, vs)| !us.is_empty() && !vs.is_empty())
+		.collect()
 }
 
 /// Compares two relations for equality.
+///
+/// The elements of a [`Set`] are always sorted, so relations over equal
+/// domains agree on the index of every element and are compared index by
+/// index. Use [`compare`] to compare the pairs of relations over different
+/// domains.
 pub fn eq<P: Relation, Q: Relation>(p: &P, q: &Q) -> bool {
 	if p.get_domain() != q.get_domain() { return false; }
 	for ix in p.ixs() {
@@ -280,6 +503,64 @@ pub fn eq<P: Relation, Q: Relation>(p: &P, q: &Q) -> bool {
 	return true;
 }
 
+/// The index in `y` of every element of `x`, if any.
+fn positions(x: &Set, y: &Set) -> Vec<Option<usize>> {
+	let mut positions = vec![None; x.cardinality()];
+	for ((ix, iy), _) in x.intersection_enumerated(y) {
+		positions[ix] = Some(iy);
+	}
+	positions
+}
+
+/// Calls `f` for every pair of `p` with the indices of its elements in
+/// the domain of `q`, until `f` returns `false`.
+fn all_pairs<P, Q, F>(p: &P, q: &Q, mut f: F) -> bool
+where P: Relation,
+      Q: Relation,
+      F: FnMut(Option<(usize, usize)>) -> bool,
+{
+	let (ixs, iys) = (positions(p.get_domain().0, q.get_domain().0), positions(p.get_domain().1, q.get_domain().1));
+	p.ixs().all(|ix| {
+		p.iys()
+			.filter(|&iy| p.eval_at(ix, iy))
+			.all(|iy| f(ixs[ix].zip(iys[iy])))
+	})
+}
+
+/// Checks whether `p ⊆ q`, i.e. every pair of `p` is a pair of `q`.
+///
+/// The pairs are compared by their elements, so the domains may differ.
+pub fn is_subrelation<P: Relation, Q: Relation>(p: &P, q: &Q) -> bool {
+	all_pairs(p, q, |i| i.is_some_and(|(jx, jy)| q.eval_at(jx, jy)))
+}
+
+/// Checks whether `p ⊂ q`, i.e. `p ⊆ q` and `p ≠ q`.
+pub fn is_strict_subrelation<P: Relation, Q: Relation>(p: &P, q: &Q) -> bool {
+	is_subrelation(p, q) && !is_subrelation(q, p)
+}
+
+/// Checks whether `p ∩ q = ∅`, i.e. `p` and `q` have no pair in common.
+pub fn is_disjoint<P: Relation, Q: Relation>(p: &P, q: &Q) -> bool {
+	all_pairs(p, q, |i| match i {
+		Some((jx, jy)) => !q.eval_at(jx, jy),
+		None => true,
+	})
+}
+
+/// Compares two relations by inclusion of their pairs,
+/// see [`is_subrelation`].
+///
+/// Returns `None` if neither relation is included in the other.
+pub fn compare<P: Relation, Q: Relation>(p: &P, q: &Q) -> Option<std::cmp::Ordering> {
+	use std::cmp::Ordering;
+	match (is_subrelation(p, q), is_subrelation(q, p)) {
+		(true, true) => Some(Ordering::Equal),
+		(true, false) => Some(Ordering::Less),
+		(false, true) => Some(Ordering::Greater),
+		(false, false) => None,
+	}
+}
+
 /// The [`Empty`] `Relation`
 #[derive(Clone, Debug)]
 pub struct Empty<'a>((&'a Set, &'a Set));
@@ -352,6 +633,81 @@ impl<P: Relation, Q: Relation> Relation for Concatenation<'_, P, Q> {
 	}
 }
 
+#[derive(Clone, Debug)]
+pub struct LeftResidual<'a, P: Relation, Q: Relation> {
+	p: LCow<'a, P>,
+	q: LCow<'a, Q>,
+}
+
+impl<'a, P: Relation, Q: Relation> LeftResidual<'a, P, Q> {
+	pub fn new<S: Into<LCow<'a, P>>, T: Into<LCow<'a, Q>>>(s: S, t: T) -> Self {
+		let p = s.into();
+		let q = t.into();
+		debug_assert_eq!(p.get_domain().0, q.get_domain().0);
+		LeftResidual { p, q }
+	}
+}
+
+impl<P: Relation, Q: Relation> Relation for LeftResidual<'_, P, Q> {
+	fn get_domain(&self) -> (&Set, &Set) {
+		(self.p.get_domain().1, self.q.get_domain().1)
+	}
+	fn eval_at(&self, iy: usize, iz: usize) -> bool {
+		// p.ixs() == q.ixs()
+		self.p.ixs().all(|ix| !self.p.eval_at(ix, iy) || self.q.eval_at(ix, iz))
+	}
+}
+
+#[derive(Clone, Debug)]
+pub struct RightResidual<'a, P: Relation, Q: Relation> {
+	p: LCow<'a, P>,
+	q: LCow<'a, Q>,
+}
+
+impl<'a, P: Relation, Q: Relation> RightResidual<'a, P, Q> {
+	pub fn new<S: Into<LCow<'a, P>>, T: Into<LCow<'a, Q>>>(s: S, t: T) -> Self {
+		let p = s.into();
+		let q = t.into();
+		debug_assert_eq!(p.get_domain().1, q.get_domain().1);
+		RightResidual { p, q }
+	}
+}
+
+impl<P: Relation, Q: Relation> Relation for RightResidual<'_, P, Q> {
+	fn get_domain(&self) -> (&Set, &Set) {
+		(self.p.get_domain().0, self.q.get_domain().0)
+	}
+	fn eval_at(&self, ix: usize, iy: usize) -> bool {
+		// p.iys() == q.iys()
+		self.q.iys().all(|iz| !self.q.eval_at(iy, iz) || self.p.eval_at(ix, iz))
+	}
+}
+
+#[derive(Clone, Debug)]
+pub struct SymmetricQuotient<'a, P: Relation, Q: Relation> {
+	p: LCow<'a, P>,
+	q: LCow<'a, Q>,
+}
+
+impl<'a, P: Relation, Q: Relation> SymmetricQuotient<'a, P, Q> {
+	pub fn new<S: Into<LCow<'a, P>>, T: Into<LCow<'a, Q>>>(s: S, t: T) -> Self {
+		let p = s.into();
+		let q = t.into();
+		debug_assert_eq!(p.get_domain().0, q.get_domain().0);
+		SymmetricQuotient { p, q }
+	}
+}
+
+impl<P: Relation, Q: Relation> Relation for SymmetricQuotient<'_, P, Q> {
+	fn get_domain(&self) -> (&Set, &Set) {
+		(self.p.get_domain().1, self.q.get_domain().1)
+	}
+	fn eval_at(&self, iy: usize, iz: usize) -> bool {
+		// p.ixs() == q.ixs()
+		self.p.ixs().all(|ix| self.p.eval_at(ix, iy) == self.q.eval_at(ix, iz))
+	}
+}
+
 #[derive(Clone, Debug)]
 pub struct Converse<'a, R: Relation> {
 	r: LCow<'a, R>,
@@ -414,9 +770,77 @@ impl<P: Relation, Q: Relation> Relation for Union<'_, P, Q> {
 	}
 }
 
+/// A relation restricted to subsets of its domain.
+#[derive(Clone, Debug)]
+pub struct Restriction<'a, R: Relation> {
+	r: LCow<'a, R>,
+	domain: (Set, Set),
+	/// The indices into the domain of `r` of the elements of `domain`.
+	indices: (Vec<usize>, Vec<usize>),
+}
+
+impl<'a, R: Relation> Restriction<'a, R> {
+	pub fn new<T: Into<LCow<'a, R>>>(t: T, u: &Set, v: &Set) -> Self {
+		let r: LCow<'a, R> = t.into();
+		let (u, ius) = restrict(r.get_domain().0, u);
+		let (v, ivs) = restrict(r.get_domain().1, v);
+		Restriction { r, domain: (u, v), indices: (ius, ivs) }
+	}
+}
+
+/// The intersection `X ∩ U` and the indices into `x` of its elements.
+fn restrict(x: &Set, u: &Set) -> (Set, Vec<usize>) {
+	let (indices, elements): (Vec<usize>, Vec<&SetElement>) = x.intersection_enumerated(u)
+		.map(|((ix, _), e)| (ix, e))
+		.unzip();
+	(elements.into_iter().cloned().collect(), indices)
+}
+
+impl<R: Relation> Relation for Restriction<'_, R> {
+	fn get_domain(&self) -> (&Set, &Set) {
+		(&self.domain.0, &self.domain.1)
+	}
+	fn eval_at(&self, iu: usize, iv: usize) -> bool {
+		self.r.eval_at(self.indices.0[iu], self.indices.1[iv])
+	}
+}
+
+/// The Kronecker product of two relations over the cartesian products of
+/// their domains, see [`Set::cartesian_product`].
+#[derive(Clone, Debug)]
+pub struct Kronecker<'a, P: Relation, Q: Relation> {
+	p: LCow<'a, P>,
+	q: LCow<'a, Q>,
+	domain: (Set, Set),
+}
+
+impl<'a, P: Relation, Q: Relation> Kronecker<'a, P, Q> {
+	pub fn new<S: Into<LCow<'a, P>>, T: Into<LCow<'a, Q>>>(s: S, t: T) -> Self {
+		let (p, q): (LCow<'a, P>, LCow<'a, Q>) = (s.into(), t.into());
+		let domain = (
+			p.get_domain().0.cartesian_product(q.get_domain().0),
+			p.get_domain().1.cartesian_product(q.get_domain().1),
+		);
+		Kronecker { p, q, domain }
+	}
+}
+
+impl<P: Relation, Q: Relation> Relation for Kronecker<'_, P, Q> {
+	fn get_domain(&self) -> (&Set, &Set) {
+		(&self.domain.0, &self.domain.1)
+	}
+	fn eval_at(&self, ixu: usize, iyv: usize) -> bool {
+		// the pairs are ordered lexicographically
+		let (nu, nv) = (self.q.get_domain().0.cardinality(), self.q.get_domain().1.cardinality());
+		self.p.eval_at(ixu / nu, iyv / nv) && self.q.eval_at(ixu % nu, iyv % nv)
+	}
+}
+
 #[cfg(test)]
 pub mod tests {
 	use super::*;
+	use crate::relation::RelationVec;
+	use crate::relation::product::DirectProduct;
 
 	pub fn relation_property_test<R>(r: &R)
 	where R: Relation + std::fmt::Debug
@@ -427,6 +851,62 @@ pub mod tests {
 		assert_eq!(r.is_function(), r.is_functional() && r.is_lefttotal());
 	}
 
+	pub fn difunctional<R>(r: &R)
+	where R: Relation + std::fmt::Debug
+	{
+		// naive difunctional closure: fixpoint of r ∪ r;rᵀ;r
+		let mut expected = RelationVec::from_relation(r);
+		loop {
+			let conv = R::converse(&expected);
+			let rr = R::concatenation(&conv, &expected);
+			let rrr = R::concatenation(&expected, &rr);
+			let next = RelationVec::from_relation(&R::union(&expected, &rrr));
+			if next == expected { break; }
+			expected = next;
+		}
+		let c = R::closure_difunctional(r);
+		assert!(c.is_difunctional());
+		assert!(eq(&c, &expected));
+
+		// the rectangles of the decomposition make up the closure
+		let rectangles = c.difunctional_decomposition().unwrap();
+		for (ix, x) in c.get_domain().0.iter().enumerate() {
+			for (iy, y) in c.get_domain().1.iter().enumerate() {
+				let n = rectangles.iter().filter(|(u, v)| u.contains(x) && v.contains(y)).count();
+				assert!(n <= 1);
+				assert_eq!(n == 1, c.eval_at(ix, iy));
+			}
+		}
+		assert_eq!(r.difunctional_decomposition().is_some(), r.is_difunctional());
+	}
+
+	pub fn ferrers<R>(r: &R)
+	where R: Relation + std::fmt::Debug
+	{
+		let conv = R::converse(r);
+		let compl = R::complement(&conv);
+		let rr = R::concatenation(&compl, r);
+		let rrr = R::concatenation(r, &rr);
+		assert_eq!(r.is_ferrers(), eq(&R::intersection(&rrr, r), &rrr));
+		assert_eq!(r.is_ferrers(), R::converse(r).is_ferrers());
+		assert_eq!(r.is_ferrers(), R::complement(r).is_ferrers());
+
+		let c = R::upper_ferrers_hull(r);
+		assert!(c.is_ferrers());
+		assert!(eq(&R::intersection(r, &c), r));
+		assert_eq!(eq(&c, r), r.is_ferrers());
+
+		// rows of the staircase form are prefixes of the rows above them
+		let (rows, columns) = c.staircase();
+		let mut previous = columns.len();
+		for ix in rows {
+			let len = columns.iter().take_while(|&&iy| c.eval_at(ix, iy)).count();
+			assert!(columns[len..].iter().all(|&iy| !c.eval_at(ix, iy)));
+			assert!(len <= previous);
+			previous = len;
+		}
+	}
+
 	pub fn complement<R>(r: &R)
 	where R: Relation + std::fmt::Debug
 	{
@@ -592,6 +1072,108 @@ pub mod tests {
 		));
 	}
 
+	pub fn residuals<R>(r: &R, s: &R, x: &R)
+	where R: Relation + std::fmt::Debug
+	{
+		let left = R::left_residual(r, s);
+		let right = R::right_residual(s, r);
+		let syq = R::symmetric_quotient(r, s);
+
+		// definitions in terms of complement, converse and concatenation
+		assert!(eq(&left, &R::complement(&R::concatenation(&R::converse(r), &R::complement(s)))));
+		assert!(eq(&right, &R::complement(&R::concatenation(&R::complement(s), &R::converse(r)))));
+		assert!(eq(&syq, &R::intersection(&left, &R::converse(&R::left_residual(s, r)))));
+		assert!(eq(&R::converse(&syq), &R::symmetric_quotient(s, r)));
+
+		// Galois correspondences: R ; X ⊆ S ⇔ X ⊆ R \ S and X ; R ⊆ S ⇔ X ⊆ S / R
+		let rx_in_s = is_subrelation(&R::concatenation(r, x), s);
+		assert_eq!(rx_in_s, is_subrelation(x, &left));
+		assert_eq!(is_subrelation(&R::concatenation(x, r), s), is_subrelation(x, &right));
+		// the residuals are the greatest solutions
+		assert!(is_subrelation(&R::concatenation(r, &left), s));
+		assert!(is_subrelation(&R::concatenation(&right, r), s));
+
+		// Schröder equivalences: R ; X ⊆ S ⇔ Rᵀ ; ¬S ⊆ ¬X ⇔ ¬S ; Xᵀ ⊆ ¬R
+		let not_s = R::complement(s);
+		assert_eq!(rx_in_s, is_subrelation(&R::concatenation(&R::converse(r), &not_s), &R::complement(x)));
+		assert_eq!(rx_in_s, is_subrelation(&R::concatenation(&not_s, &R::converse(x)), &R::complement(r)));
+	}
+
+	pub fn kronecker<R>(a: &R, b: &R, c: &R)
+	where R: Relation + std::fmt::Debug
+	{
+		let ab = R::kronecker(a, b);
+		for (x, u) in a.get_domain().0.iter().flat_map(|x| b.get_domain().0.iter().map(move |u| (x, u))) {
+			for (y, v) in a.get_domain().1.iter().flat_map(|y| b.get_domain().1.iter().map(move |v| (y, v))) {
+				let (xu, yv) = (SetElement::from((x.clone(), u.clone())), SetElement::from((y.clone(), v.clone())));
+				assert_eq!(ab.eval(&xu, &yv), a.eval(x, y) && b.eval(u, v));
+			}
+		}
+
+		// the parallel product π ; R ; π'ᵀ ∩ ρ ; S ; ρ'ᵀ
+		let source = DirectProduct::new(a.get_domain().0, b.get_domain().0);
+		let target = DirectProduct::new(a.get_domain().1, b.get_domain().1);
+		let (pi, rho) = (source.pi(), source.rho());
+		let (pi_y, rho_y) = (target.pi(), target.rho());
+		assert!(eq(&ab, &R::intersection(
+			&R::concatenation(&R::concatenation(&pi, a), &R::converse(&pi_y)),
+			&R::concatenation(&R::concatenation(&rho, b), &R::converse(&rho_y)),
+		)));
+
+		// (A ⊗ B)ᵀ = Aᵀ ⊗ Bᵀ and (A ⊗ B) ; (C ⊗ C) = A ; C ⊗ B ; C
+		assert!(eq(&R::converse(&ab), &R::kronecker(&R::converse(a), &R::converse(b))));
+		let cc = R::kronecker(c, c);
+		let (ac, bc) = (R::concatenation(a, c), R::concatenation(b, c));
+		assert!(eq(&R::concatenation(&ab, &cc), &R::kronecker(&ac, &bc)));
+	}
+
+	pub fn restriction<R>(r: &R, u: &Set, v: &Set)
+	where R: Relation + std::fmt::Debug
+	{
+		let (x, y) = r.get_domain();
+		let source = R::restrict_source(r, u);
+		let restricted = R::restrict_target(&source, v);
+		assert_eq!(restricted.get_domain().0, &x.intersection(u).cloned().collect::<Set>());
+		assert_eq!(restricted.get_domain().1, &y.intersection(v).cloned().collect::<Set>());
+		for x in restricted.get_domain().0.iter() {
+			for y in restricted.get_domain().1.iter() {
+				assert_eq!(restricted.eval(x, y), r.eval(x, y));
+			}
+		}
+		assert_eq!(source.get_domain().1, y);
+		assert!(eq(&restricted, &R::restrict_source(&R::restrict_target(r, v), u)));
+		// restricting to the whole domain changes nothing
+		assert!(eq(&R::restrict_target(&R::restrict_source(r, x), y), r));
+		if r.is_homogeneous() {
+			assert!(eq(&R::induced_subrelation(r, u), &R::restrict_target(&R::restrict_source(r, u), u)));
+		}
+	}
+
+	pub fn inclusion<R>(r: &R, s: &R)
+	where R: Relation + std::fmt::Debug
+	{
+		use std::cmp::Ordering;
+		let (both, either) = (R::intersection(r, s), R::union(r, s));
+		assert!(is_subrelation(&both, r) && is_subrelation(r, &either));
+		assert!(is_subrelation(&R::empty(r.get_domain()), r));
+		assert!(!is_strict_subrelation(r, r));
+		assert_eq!(is_subrelation(r, s), eq(&both, r));
+		assert_eq!(is_strict_subrelation(r, s), is_subrelation(r, s) && !eq(r, s));
+		assert_eq!(is_disjoint(r, s), eq(&both, &R::empty(r.get_domain())));
+		assert!(is_disjoint(r, &R::complement(r)));
+		assert_eq!(compare(r, r), Some(Ordering::Equal));
+		assert_eq!(compare(r, s), compare(s, r).map(Ordering::reverse));
+		assert_eq!(compare(r, s) == Some(Ordering::Equal), eq(r, s));
+
+		// restricting the domain does not change the pairs within it
+		let source = r.source();
+		let restricted = R::restrict_source(r, &source);
+		assert_eq!(compare(&restricted, r), Some(Ordering::Equal));
+		let sub = R::restrict_source(s, &source);
+		assert!(is_subrelation(&sub, s));
+		assert_eq!(is_subrelation(s, &sub), s.source().is_subset(&source));
+	}
+
 	pub fn de_morgan<R>(a: &R, b: &R)
 	where R: Relation + std::fmt::Debug
 	{
@@ -604,4 +1186,19 @@ pub mod tests {
 			&R::union(&R::complement(a), &R::complement(b)),
 		));
 	}
+
+	/// The characters of `s` as a sequence of elements.
+	pub fn elements(s: &str) -> Vec<SetElement> {
+		s.chars().map(|c| SetElement::from(&c)).collect()
+	}
+
+	/// The set of the characters of `s`, e.g. `{a b c}` for `"abc"`.
+	pub fn set(s: &str) -> Set {
+		s.chars().collect::<Vec<_>>().iter().collect()
+	}
+
+	/// The set of the whitespace-separated words of `s`.
+	pub fn words(s: &str) -> Set {
+		s.split_whitespace().map(String::from).collect()
+	}
 }
@@ -0,0 +1,555 @@
+//! A binary `Relation`, represented as a reduced ordered binary decision diagram.
+
+use std::collections::HashMap;
+use std::vec::Vec;
+use std::fmt;
+
+use crate::set::Set;
+use crate::relation::{Relation, Endorelation, RelationMut, RelationVec};
+
+/// Maximum number of bits of an index.
+const MAX_BITS: u32 = 64;
+/// Number of index-slots a variable may belong to.
+/// Slot `0` and `1` encode `ix` and `iy`, slot `2` is used as temporary
+/// storage while calculating a concatenation.
+const SLOTS: u32 = 3;
+/// Variable of the terminal nodes, ordered after all other variables.
+const LEAF: u32 = u32::MAX;
+
+const FALSE: usize = 0;
+const TRUE: usize = 1;
+
+/// Return the variable encoding bit `bit` of the index in slot `slot`.
+///
+/// Variables of different slots are interleaved, the most significant bits
+/// are placed at the top of the diagram.
+fn var(slot: u32, bit: u32) -> u32 {
+	(MAX_BITS - 1 - bit) * SLOTS + slot
+}
+fn var_slot(var: u32) -> u32 {
+	var % SLOTS
+}
+fn var_bit(var: u32) -> u32 {
+	MAX_BITS - 1 - var / SLOTS
+}
+/// Number of bits required to encode the indices `0..n`.
+fn bits(n: usize) -> u32 {
+	match n {
+		0 | 1 => 0,
+		n => usize::BITS - (n - 1).leading_zeros(),
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Node {
+	var: u32,
+	lo: usize,
+	hi: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Op {
+	And,
+	Or,
+	AndNot,
+}
+
+impl Op {
+	fn eval(self, p: bool, q: bool) -> bool {
+		match self {
+			Op::And => p && q,
+			Op::Or => p || q,
+			Op::AndNot => p && !q,
+		}
+	}
+}
+
+/// A reduced ordered binary decision diagram.
+/// The nodes are stored in post-order, which makes the representation of a
+/// boolean function unique.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Bdd {
+	nodes: Vec<Node>,
+	root: usize,
+}
+
+/// A table of nodes used to build new diagrams.
+struct Builder {
+	nodes: Vec<Node>,
+	unique: HashMap<Node, usize>,
+	cache: HashMap<(Op, usize, usize), usize>,
+}
+
+impl Builder {
+	fn new() -> Self {
+		let leaf = |i| Node { var: LEAF, lo: i, hi: i };
+		Builder {
+			nodes: vec![leaf(FALSE), leaf(TRUE)],
+			unique: HashMap::new(),
+			cache: HashMap::new(),
+		}
+	}
+	/// Return the node `(var ? hi : lo)`, creating it if necessary.
+	fn mk(&mut self, var: u32, lo: usize, hi: usize) -> usize {
+		if lo == hi { return lo; }
+		let node = Node { var, lo, hi };
+		if let Some(&i) = self.unique.get(&node) { return i; }
+		self.nodes.push(node.clone());
+		self.unique.insert(node, self.nodes.len() - 1);
+		self.nodes.len() - 1
+	}
+	/// Copy the diagram rooted at `f` of `bdd` into this table.
+	fn import(&mut self, bdd: &Bdd) -> usize {
+		fn rec(b: &mut Builder, nodes: &[Node], f: usize, memo: &mut HashMap<usize, usize>) -> usize {
+			if f <= TRUE { return f; }
+			if let Some(&r) = memo.get(&f) { return r; }
+			let lo = rec(b, nodes, nodes[f].lo, memo);
+			let hi = rec(b, nodes, nodes[f].hi, memo);
+			let r = b.mk(nodes[f].var, lo, hi);
+			memo.insert(f, r);
+			r
+		}
+		rec(self, &bdd.nodes, bdd.root, &mut HashMap::new())
+	}
+	/// Extract the diagram rooted at `f`, dropping all unreachable nodes.
+	fn finish(&self, f: usize) -> Bdd {
+		let mut b = Builder::new();
+		let root = b.import(&Bdd { nodes: self.nodes.clone(), root: f });
+		Bdd { nodes: b.nodes, root }
+	}
+
+	fn apply(&mut self, op: Op, f: usize, g: usize) -> usize {
+		if f <= TRUE && g <= TRUE {
+			return if op.eval(f == TRUE, g == TRUE) { TRUE } else { FALSE };
+		}
+		if let Some(&r) = self.cache.get(&(op, f, g)) { return r; }
+		let (nf, ng) = (self.nodes[f].clone(), self.nodes[g].clone());
+		let var = Ord::min(nf.var, ng.var);
+		let (f0, f1) = if nf.var == var { (nf.lo, nf.hi) } else { (f, f) };
+		let (g0, g1) = if ng.var == var { (ng.lo, ng.hi) } else { (g, g) };
+		let lo = self.apply(op, f0, g0);
+		let hi = self.apply(op, f1, g1);
+		let r = self.mk(var, lo, hi);
+		self.cache.insert((op, f, g), r);
+		r
+	}
+	/// `(var ? hi : lo)` for arbitrary `lo` and `hi`.
+	fn ite(&mut self, var: u32, hi: usize, lo: usize) -> usize {
+		let v = self.mk(var, FALSE, TRUE);
+		let hi = self.apply(Op::And, v, hi);
+		let lo = self.apply(Op::AndNot, lo, v);
+		self.apply(Op::Or, hi, lo)
+	}
+	/// Existentially quantify all variables of `slot`.
+	fn exists(&mut self, f: usize, slot: u32) -> usize {
+		fn rec(b: &mut Builder, f: usize, slot: u32, memo: &mut HashMap<usize, usize>) -> usize {
+			if f <= TRUE { return f; }
+			if let Some(&r) = memo.get(&f) { return r; }
+			let node = b.nodes[f].clone();
+			let lo = rec(b, node.lo, slot, memo);
+			let hi = rec(b, node.hi, slot, memo);
+			let r = if var_slot(node.var) == slot {
+				b.apply(Op::Or, lo, hi)
+			} else {
+				b.mk(node.var, lo, hi)
+			};
+			memo.insert(f, r);
+			r
+		}
+		rec(self, f, slot, &mut HashMap::new())
+	}
+	/// Move the variables of every slot `s` to slot `map[s]`.
+	fn rename(&mut self, f: usize, map: [u32; SLOTS as usize]) -> usize {
+		fn rec(b: &mut Builder, f: usize, map: [u32; SLOTS as usize], memo: &mut HashMap<usize, usize>) -> usize {
+			if f <= TRUE { return f; }
+			if let Some(&r) = memo.get(&f) { return r; }
+			let node = b.nodes[f].clone();
+			let lo = rec(b, node.lo, map, memo);
+			let hi = rec(b, node.hi, map, memo);
+			let r = b.ite(var(map[var_slot(node.var) as usize], var_bit(node.var)), hi, lo);
+			memo.insert(f, r);
+			r
+		}
+		rec(self, f, map, &mut HashMap::new())
+	}
+	/// The function `i < n` for the index `i` stored in `slot`.
+	fn less_than(&mut self, slot: u32, n: usize) -> usize {
+		let width = bits(n);
+		if width < usize::BITS && n >= 1 << width { return TRUE; }
+		let mut node = FALSE;
+		for bit in 0..width {
+			node = if (n >> bit) & 1 == 1 {
+				self.mk(var(slot, bit), TRUE, node)
+			} else {
+				self.mk(var(slot, bit), node, FALSE)
+			};
+		}
+		node
+	}
+	/// The function `i = j` for the indices `i` and `j` stored in `p` and `q`.
+	fn equal(&mut self, p: u32, q: u32, width: u32) -> usize {
+		let mut node = TRUE;
+		for bit in 0..width {
+			let (vp, vq) = (var(p, bit), var(q, bit));
+			let (first, second) = (Ord::min(vp, vq), Ord::max(vp, vq));
+			let lo = self.mk(second, node, FALSE);
+			let hi = self.mk(second, FALSE, node);
+			node = self.mk(first, lo, hi);
+		}
+		node
+	}
+	/// The function that is only `true` for `(ix, iy)`.
+	fn minterm(&mut self, ix: usize, iy: usize, width: (u32, u32)) -> usize {
+		let mut vars: Vec<(u32, bool)> = (0..width.0).map(|b| (var(0, b), (ix >> b) & 1 == 1))
+			.chain((0..width.1).map(|b| (var(1, b), (iy >> b) & 1 == 1)))
+			.collect();
+		vars.sort_unstable();
+		vars.iter().rev().fold(TRUE, |node, &(v, set)| {
+			if set { self.mk(v, FALSE, node) } else { self.mk(v, node, FALSE) }
+		})
+	}
+	/// The function `ix < |X| ∧ iy < |Y|` of all valid indices of the domain.
+	fn valid(&mut self, domain: (&Set, &Set)) -> usize {
+		let x = self.less_than(0, domain.0.cardinality());
+		let y = self.less_than(1, domain.1.cardinality());
+		self.apply(Op::And, x, y)
+	}
+}
+
+/// A binary `Relation`, represented as a reduced ordered binary decision diagram
+/// (ROBDD).
+/// The indices `ix` and `iy` are encoded bitwise as boolean variables,
+/// interleaved from the most significant to the least significant bit.
+///
+/// As ROBDDs are canonical, the size of the representation depends on the
+/// structure of the relation rather than the size of its domain, which makes
+/// this representation suitable for huge but regular relations.
+/// Two `RelationBdd` over the same domain compare equal iff they contain the
+/// same pairs.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RelationBdd {
+	domain: (Set, Set),
+	bdd: Bdd,
+}
+
+impl RelationBdd {
+	/// Create the empty [`RelationBdd`] over the given domain.
+	pub fn new(domain: (Set, Set)) -> Self {
+		RelationBdd {
+			domain,
+			bdd: Builder::new().finish(FALSE),
+		}
+	}
+	/// Create a new [`RelationBdd`] from the given domain and pairs of indices.
+	pub fn from_pairs<I>(domain: (Set, Set), pairs: I) -> Self
+	where I: IntoIterator<Item = (usize, usize)>,
+	{
+		let width = (bits(domain.0.cardinality()), bits(domain.1.cardinality()));
+		let mut b = Builder::new();
+		let mut root = FALSE;
+		for (ix, iy) in pairs {
+			assert!(ix < domain.0.cardinality() && iy < domain.1.cardinality());
+			let m = b.minterm(ix, iy, width);
+			root = b.apply(Op::Or, root, m);
+		}
+		RelationBdd {
+			bdd: b.finish(root),
+			domain,
+		}
+	}
+	/// Create the identity [`RelationBdd`] `I` over the given set, where `xIy ⇔ x = y`.
+	pub fn diagonal(set: Set) -> Self {
+		let mut b = Builder::new();
+		let eq = b.equal(0, 1, bits(set.cardinality()));
+		let valid = b.less_than(0, set.cardinality());
+		let root = b.apply(Op::And, eq, valid);
+		RelationBdd {
+			bdd: b.finish(root),
+			domain: (set.clone(), set),
+		}
+	}
+	/// Create a new [`RelationBdd`] from the given `Relation`.
+	pub fn from_relation<R: Relation>(r: &R) -> Self {
+		fn rec<R: Relation>(b: &mut Builder, r: &R, vars: &[u32], ix: usize, iy: usize) -> usize {
+			let (nx, ny) = (r.get_domain().0.cardinality(), r.get_domain().1.cardinality());
+			if ix >= nx || iy >= ny { return FALSE; }
+			let (&v, vars) = match vars.split_first() {
+				None => return if r.eval_at(ix, iy) { TRUE } else { FALSE },
+				Some(split) => split,
+			};
+			let bit = 1 << var_bit(v);
+			let lo = rec(b, r, vars, ix, iy);
+			let hi = match var_slot(v) {
+				0 => rec(b, r, vars, ix | bit, iy),
+				_ => rec(b, r, vars, ix, iy | bit),
+			};
+			b.mk(v, lo, hi)
+		}
+		let (set1, set2) = r.get_domain();
+		let mut vars: Vec<u32> = (0..bits(set1.cardinality())).map(|b| var(0, b))
+			.chain((0..bits(set2.cardinality())).map(|b| var(1, b)))
+			.collect();
+		vars.sort_unstable();
+		let mut b = Builder::new();
+		let root = rec(&mut b, r, &vars, 0, 0);
+		RelationBdd {
+			domain: (set1.clone(), set2.clone()),
+			bdd: b.finish(root),
+		}
+	}
+
+	/// Number of inner nodes of the decision diagram.
+	pub fn node_count(&self) -> usize {
+		self.bdd.nodes.len() - 2
+	}
+
+	/// The union of two [`RelationBdd`], computed on the decision diagrams.
+	///
+	/// See [`Relation::union`] for the lazy variant.
+	pub fn union_bdd(&self, other: &RelationBdd) -> RelationBdd {
+		assert_eq!(self.domain, other.domain);
+		self.apply(Op::Or, other)
+	}
+	/// The intersection of two [`RelationBdd`], computed on the decision diagrams.
+	///
+	/// See [`Relation::intersection`] for the lazy variant.
+	pub fn intersection_bdd(&self, other: &RelationBdd) -> RelationBdd {
+		assert_eq!(self.domain, other.domain);
+		self.apply(Op::And, other)
+	}
+	/// The complement of a [`RelationBdd`], computed on the decision diagram.
+	///
+	/// See [`Relation::complement`] for the lazy variant.
+	pub fn complement_bdd(&self) -> RelationBdd {
+		let mut b = Builder::new();
+		let f = b.import(&self.bdd);
+		let valid = b.valid(self.get_domain());
+		let root = b.apply(Op::AndNot, valid, f);
+		RelationBdd {
+			domain: self.domain.clone(),
+			bdd: b.finish(root),
+		}
+	}
+	/// The converse of a [`RelationBdd`], computed on the decision diagram.
+	///
+	/// See [`Relation::converse`] for the lazy variant.
+	pub fn converse_bdd(&self) -> RelationBdd {
+		let mut b = Builder::new();
+		let f = b.import(&self.bdd);
+		let root = b.rename(f, [1, 0, 2]);
+		RelationBdd {
+			domain: (self.domain.1.clone(), self.domain.0.clone()),
+			bdd: b.finish(root),
+		}
+	}
+	/// The concatenation of two [`RelationBdd`], computed on the decision diagrams.
+	///
+	/// See [`Relation::concatenation`] for the lazy variant.
+	pub fn concatenation_bdd(&self, other: &RelationBdd) -> RelationBdd {
+		assert_eq!(self.domain.1, other.domain.0);
+		let mut b = Builder::new();
+		// move y to slot 2 in both diagrams: p(x, y) ∧ q(y, z)
+		let p = b.import(&self.bdd);
+		let p = b.rename(p, [0, 2, 1]);
+		let q = b.import(&other.bdd);
+		let q = b.rename(q, [2, 1, 0]);
+		let pq = b.apply(Op::And, p, q);
+		let root = b.exists(pq, 2);
+		RelationBdd {
+			domain: (self.domain.0.clone(), other.domain.1.clone()),
+			bdd: b.finish(root),
+		}
+	}
+
+	fn apply(&self, op: Op, other: &RelationBdd) -> RelationBdd {
+		let mut b = Builder::new();
+		let p = b.import(&self.bdd);
+		let q = b.import(&other.bdd);
+		let root = b.apply(op, p, q);
+		RelationBdd {
+			domain: self.domain.clone(),
+			bdd: b.finish(root),
+		}
+	}
+}
+
+impl Relation for RelationBdd {
+	fn get_domain(&self) -> (&Set, &Set) {
+		(&self.domain.0, &self.domain.1)
+	}
+	fn eval_at(&self, ix: usize, iy: usize) -> bool {
+		let nodes = &self.bdd.nodes;
+		let mut f = self.bdd.root;
+		while f > TRUE {
+			let i = if var_slot(nodes[f].var) == 0 { ix } else { iy };
+			f = if (i >> var_bit(nodes[f].var)) & 1 == 1 { nodes[f].hi } else { nodes[f].lo };
+		}
+		f == TRUE
+	}
+}
+
+impl Endorelation for RelationBdd {}
+
+impl RelationMut for RelationBdd {
+	fn set_at(&mut self, ix: usize, iy: usize, value: bool) {
+		assert!(ix < self.domain.0.cardinality() && iy < self.domain.1.cardinality());
+		let width = (bits(self.domain.0.cardinality()), bits(self.domain.1.cardinality()));
+		let mut b = Builder::new();
+		let f = b.import(&self.bdd);
+		let m = b.minterm(ix, iy, width);
+		let root = if value { b.apply(Op::Or, f, m) } else { b.apply(Op::AndNot, f, m) };
+		self.bdd = b.finish(root);
+	}
+	fn union_with<R: Relation>(&mut self, other: &R) {
+		debug_assert_eq!(self.get_domain(), other.get_domain());
+		*self = self.union_bdd(&RelationBdd::from_relation(other));
+	}
+	fn intersect_with<R: Relation>(&mut self, other: &R) {
+		debug_assert_eq!(self.get_domain(), other.get_domain());
+		*self = self.intersection_bdd(&RelationBdd::from_relation(other));
+	}
+	fn complement_in_place(&mut self) {
+		*self = self.complement_bdd();
+	}
+}
+
+impl From<RelationVec> for RelationBdd {
+	fn from(r: RelationVec) -> Self {
+		RelationBdd::from_relation(&r)
+	}
+}
+
+impl From<RelationBdd> for RelationVec {
+	fn from(r: RelationBdd) -> Self {
+		let table = r.ixs()
+			.flat_map(|ix| r.iys().map(move |iy| (ix, iy)))
+			.map(|(ix, iy)| r.eval_at(ix, iy))
+			.collect();
+		RelationVec::new(r.domain, table)
+	}
+}
+
+impl fmt::Display for RelationBdd {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.domain.0)?;
+		write!(f, " x ")?;
+		write!(f, "{}", self.domain.1)?;
+		writeln!(f, " :")?;
+		for ix in self.ixs() {
+			write!(f, "|")?;
+			for iy in self.iys() {
+				write!(f, "{}", if self.eval_at(ix, iy) { 1 } else { 0 })?;
+			}
+			writeln!(f, "|")?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::relation;
+	use crate::relation::relation_vec::tests::{
+		domain_arbitrary, domain_homogeneous, relation_arbitrary, three_rels,
+	};
+	use proptest::prelude::*;
+
+	#[test]
+	fn bits_of_domain() {
+		assert_eq!(bits(0), 0);
+		assert_eq!(bits(1), 0);
+		assert_eq!(bits(2), 1);
+		assert_eq!(bits(5), 3);
+		assert_eq!(bits(8), 3);
+		assert_eq!(bits(9), 4);
+	}
+	#[test]
+	fn diagonal_large() {
+		let n: Set = (0..1 << 12).collect();
+		let id = RelationBdd::diagonal(n.clone());
+		// linear in the number of bits, not the number of elements
+		assert!(id.node_count() <= 3 * 12);
+		assert!(id.eval_at(4000, 4000));
+		assert!(!id.eval_at(4000, 4001));
+		let r = RelationBdd::from_pairs((n.clone(), n), vec![(1, 2), (2, 3), (4000, 7)]);
+		assert_eq!(r.concatenation_bdd(&id), r);
+		let rr = r.concatenation_bdd(&r);
+		assert!(rr.eval_at(1, 3));
+		assert!(!rr.eval_at(2, 3));
+		assert_eq!(RelationBdd::from_pairs(rr.domain.clone(), vec![(1, 3)]), rr);
+	}
+	#[test]
+	fn complement_masks_domain() {
+		let x: Set = (0..3).collect();
+		let y: Set = (0..5).collect();
+		let r = RelationBdd::new((x, y));
+		let c = r.complement_bdd();
+		assert!(relation::eq(&c, &RelationBdd::universal(c.get_domain())));
+		assert_eq!(c.complement_bdd(), r);
+	}
+	#[test]
+	fn diagonal_equals_identity() {
+		let s: Set = (0..6).collect();
+		let id = RelationBdd::diagonal(s.clone());
+		assert!(relation::eq(&id, &RelationBdd::identity((&s, &s))));
+	}
+
+	proptest! {
+		#[test]
+		fn new_from_relation_vec(v in relation_arbitrary(domain_arbitrary())) {
+			let r = RelationBdd::from(v.clone());
+			assert!(relation::eq(&r, &v));
+			assert_eq!(RelationVec::from(r), v);
+		}
+		#[test]
+		fn from_pairs(v in relation_arbitrary(domain_arbitrary())) {
+			let pairs = v.ixs()
+				.flat_map(|ix| v.iys().map(move |iy| (ix, iy)))
+				.filter(|&(ix, iy)| v.eval_at(ix, iy));
+			let r = RelationBdd::from_pairs((v.get_domain().0.clone(), v.get_domain().1.clone()), pairs);
+			assert_eq!(r, RelationBdd::from(v));
+		}
+		#[test]
+		fn union((p, q, _) in three_rels()) {
+			let (r, s) = (RelationBdd::from(p.clone()), RelationBdd::from(q.clone()));
+			assert!(relation::eq(&r.union_bdd(&s), &RelationVec::union(&p, &q)));
+			// the lazy combinators of the Relation trait are not shadowed
+			assert!(relation::eq(&RelationBdd::union(&r, &q), &RelationVec::union(&p, &q)));
+		}
+		#[test]
+		fn intersection((p, q, _) in three_rels()) {
+			let (r, s) = (RelationBdd::from(p.clone()), RelationBdd::from(q.clone()));
+			assert!(relation::eq(&r.intersection_bdd(&s), &RelationVec::intersection(&p, &q)));
+		}
+		#[test]
+		fn complement(v in relation_arbitrary(domain_arbitrary())) {
+			let r = RelationBdd::from(v.clone());
+			assert!(relation::eq(&r.complement_bdd(), &RelationVec::complement(&v)));
+		}
+		#[test]
+		fn converse(v in relation_arbitrary(domain_arbitrary())) {
+			let r = RelationBdd::from(v.clone());
+			assert!(relation::eq(&r.converse_bdd(), &RelationVec::converse(&v)));
+		}
+		#[test]
+		fn concatenation((p, q, _) in three_rels()) {
+			let (r, s) = (RelationBdd::from(p.clone()), RelationBdd::from(q.clone()));
+			assert!(relation::eq(&r.concatenation_bdd(&s), &RelationVec::concatenation(&p, &q)));
+			assert!(relation::eq(&RelationBdd::concatenation(&r, &q), &RelationVec::concatenation(&p, &q)));
+		}
+		#[test]
+		fn endorelation_properties(v in relation_arbitrary(domain_homogeneous())) {
+			relation::endorelation::tests::endorelation_property_test(&RelationBdd::from(v));
+		}
+	}
+
+	proptest! {
+		// every single edit rebuilds the diagram
+		#![proptest_config(ProptestConfig::with_cases(16))]
+		#[test]
+		fn relation_mut((p, q, _) in three_rels()) {
+			relation::relation_mut::tests::relation_mut_test(&RelationBdd::from(p), &q);
+		}
+	}
+}
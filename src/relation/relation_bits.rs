@@ -0,0 +1,328 @@
+//! A binary `Relation`, represented as a bit-packed incidence matrix.
+
+use std::vec::Vec;
+use std::fmt;
+
+use crate::set::Set;
+use crate::relation::{Relation, Endorelation, RelationMut, RelationVec};
+
+/// Number of bits stored in a single word of the table.
+pub(crate) const WORD_BITS: usize = 64;
+
+/// A binary `Relation`, represented as a bit-packed incidence matrix.
+/// Every row of the incidence matrix is stored as a sequence of `u64` words,
+/// where bit `iy % 64` of word `iy / 64` holds the value of `(ix, iy)`.
+/// Unused bits at the end of a row are always `0`.
+///
+/// Compared to a [`RelationVec`] this needs an eighth of the memory and
+/// allows union, intersection and complement to work on 64 cells at once.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RelationBits {
+	domain: (Set, Set),
+	/// Number of words per row.
+	words: usize,
+	table: Vec<u64>,
+}
+
+impl RelationBits {
+	/// Create the empty [`RelationBits`] over the given domain.
+	pub fn new(domain: (Set, Set)) -> Self {
+		let words = domain.1.cardinality().div_ceil(WORD_BITS);
+		let table = vec![0; domain.0.cardinality() * words];
+		RelationBits {
+			domain,
+			words,
+			table,
+		}
+	}
+	/// Create a new [`RelationBits`] from the given `Relation`.
+	pub fn from_relation<R: Relation>(r: &R) -> Self {
+		let (set1, set2) = r.get_domain();
+		let mut bits = RelationBits::new((set1.clone(), set2.clone()));
+
+		for ix in r.ixs() {
+			for iy in r.iys() {
+				if r.eval_at(ix, iy) {
+					bits.set_at(ix, iy, true);
+				}
+			}
+		}
+
+		bits
+	}
+
+	/// The words of the row `ix` of the incidence matrix.
+	pub fn row(&self, ix: usize) -> &[u64] {
+		&self.table[ix * self.words..(ix + 1) * self.words]
+	}
+	fn row_mut(&mut self, ix: usize) -> &mut [u64] {
+		&mut self.table[ix * self.words..(ix + 1) * self.words]
+	}
+	/// Number of pairs contained in the relation.
+	pub fn count(&self) -> usize {
+		self.table.iter().map(|w| w.count_ones() as usize).sum()
+	}
+
+	/// Mask of the bits in the last word of a row that belong to the domain.
+	fn last_word_mask(&self) -> u64 {
+		match self.domain.1.cardinality() % WORD_BITS {
+			0 => !0,
+			n => (1 << n) - 1,
+		}
+	}
+
+	/// The union of two [`RelationBits`], computed word by word.
+	///
+	/// See [`Relation::union`] for the lazy variant.
+	pub fn union_bits(&self, other: &RelationBits) -> RelationBits {
+		assert_eq!(self.domain, other.domain);
+		self.zip_with(other, |p, q| p | q)
+	}
+	/// The intersection of two [`RelationBits`], computed word by word.
+	///
+	/// See [`Relation::intersection`] for the lazy variant.
+	pub fn intersection_bits(&self, other: &RelationBits) -> RelationBits {
+		assert_eq!(self.domain, other.domain);
+		self.zip_with(other, |p, q| p & q)
+	}
+	/// The complement of a [`RelationBits`], computed word by word.
+	///
+	/// See [`Relation::complement`] for the lazy variant.
+	pub fn complement_bits(&self) -> RelationBits {
+		let mut r = self.clone();
+		r.complement_in_place();
+		r
+	}
+	/// The converse of a [`RelationBits`].
+	///
+	/// See [`Relation::converse`] for the lazy variant.
+	pub fn converse_bits(&self) -> RelationBits {
+		let mut r = RelationBits::new((self.domain.1.clone(), self.domain.0.clone()));
+		for ix in self.ixs() {
+			for iy in ones(self.row(ix)) {
+				r.set_at(iy, ix, true);
+			}
+		}
+		r
+	}
+
+	/// Add the pairs `(ix, iy)` for all bits `iy` set in `words` to the relation.
+	pub(crate) fn union_row_with(&mut self, ix: usize, words: &[u64]) {
+		for (w, &v) in self.row_mut(ix).iter_mut().zip(words.iter()) {
+			*w |= v;
+		}
+	}
+	/// Replace the relation by its transitive closure using Warshall's
+	/// algorithm, where every row is updated 64 cells at a time.
+	pub(crate) fn warshall(&mut self) {
+		debug_assert!(self.is_homogeneous());
+		let mut k_row = vec![0; self.words];
+		for k in self.ixs() {
+			k_row.copy_from_slice(self.row(k));
+			for i in self.ixs() {
+				if self.eval_at(i, k) {
+					self.union_row_with(i, &k_row);
+				}
+			}
+		}
+	}
+
+	fn zip_with<F: Fn(u64, u64) -> u64>(&self, other: &RelationBits, f: F) -> RelationBits {
+		RelationBits {
+			domain: self.domain.clone(),
+			words: self.words,
+			table: self.table.iter().zip(other.table.iter())
+				.map(|(&p, &q)| f(p, q))
+				.collect(),
+		}
+	}
+}
+
+/// Check whether the bit at position `i` in a sequence of words is set.
+pub(crate) fn contains(words: &[u64], i: usize) -> bool {
+	(words[i / WORD_BITS] >> (i % WORD_BITS)) & 1 == 1
+}
+
+/// Iterate the positions of all set bits in a sequence of words.
+pub(crate) fn ones(words: &[u64]) -> impl Iterator<Item = usize> + '_ {
+	words.iter().enumerate().flat_map(|(i, &w)| {
+		let mut w = w;
+		std::iter::from_fn(move || {
+			if w == 0 { return None; }
+			let bit = w.trailing_zeros() as usize;
+			w &= w - 1;
+			Some(i * WORD_BITS + bit)
+		})
+	})
+}
+
+impl Relation for RelationBits {
+	fn get_domain(&self) -> (&Set, &Set) {
+		(&self.domain.0, &self.domain.1)
+	}
+	fn eval_at(&self, ix: usize, iy: usize) -> bool {
+		let w = self.table[ix * self.words + iy / WORD_BITS];
+		(w >> (iy % WORD_BITS)) & 1 == 1
+	}
+}
+
+impl Endorelation for RelationBits {}
+
+impl RelationMut for RelationBits {
+	fn set_at(&mut self, ix: usize, iy: usize, value: bool) {
+		assert!(iy < self.domain.1.cardinality());
+		let i = ix * self.words + iy / WORD_BITS;
+		let mask = 1 << (iy % WORD_BITS);
+		if value {
+			self.table[i] |= mask;
+		} else {
+			self.table[i] &= !mask;
+		}
+	}
+	fn complement_in_place(&mut self) {
+		if self.words == 0 { return; }
+		let mask = self.last_word_mask();
+		for row in self.table.chunks_mut(self.words) {
+			for w in row.iter_mut() {
+				*w = !*w;
+			}
+			if let Some(last) = row.last_mut() {
+				*last &= mask;
+			}
+		}
+	}
+}
+
+impl From<RelationVec> for RelationBits {
+	fn from(r: RelationVec) -> Self {
+		RelationBits::from_relation(&r)
+	}
+}
+
+impl From<RelationBits> for RelationVec {
+	fn from(r: RelationBits) -> Self {
+		let table = r.ixs()
+			.flat_map(|ix| r.iys().map(move |iy| (ix, iy)))
+			.map(|(ix, iy)| r.eval_at(ix, iy))
+			.collect();
+		RelationVec::new(r.domain, table)
+	}
+}
+
+impl fmt::Display for RelationBits {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.domain.0)?;
+		write!(f, " x ")?;
+		write!(f, "{}", self.domain.1)?;
+		writeln!(f, " :")?;
+		for ix in self.ixs() {
+			write!(f, "|")?;
+			for iy in self.iys() {
+				write!(f, "{}", if self.eval_at(ix, iy) { 1 } else { 0 })?;
+			}
+			writeln!(f, "|")?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::relation;
+	use crate::relation::relation_vec::tests::{
+		domain_arbitrary, domain_homogeneous, relation_arbitrary, three_rels,
+	};
+	use proptest::prelude::*;
+
+	#[test]
+	fn eval_at_multiple_words() {
+		let n100: Vec<u8> = (1..=100).collect();
+		let v = RelationVec::from_predicate(&n100, |(&x, &y)| y % x == 0);
+		let r = RelationBits::from_relation(&v);
+		assert!(relation::eq(&r, &v));
+		assert_eq!(r.row(0).len(), 2);
+		assert_eq!(r.count(), (1..=100).map(|x| 100 / x).sum());
+		assert!(r.is_partial_order());
+	}
+	#[test]
+	fn complement_multiple_words() {
+		let n70: Vec<u8> = (0..70).collect();
+		let r = RelationBits::from_relation(&RelationVec::from_predicate(&n70, |_| false));
+		let c = r.complement_bits();
+		assert_eq!(c.count(), 70 * 70);
+		assert!(relation::eq(&c, &RelationBits::universal(c.get_domain())));
+	}
+	#[test]
+	fn converse_heterogeneous() {
+		let x: Set = (0..3).collect();
+		let y: Set = ['a', 'b'].iter().collect();
+		let v = RelationVec::new((x, y), vec![
+			true,  false,
+			false, true,
+			true,  true,
+		]);
+		let r = RelationBits::from(v.clone());
+		assert!(relation::eq(&r.converse_bits(), &RelationVec::converse(&v)));
+	}
+	#[test]
+	#[should_panic]
+	fn set_at_padding() {
+		// the unused bits of a row must stay 0
+		let x: Set = (0..3).collect();
+		let mut r = RelationBits::new((x.clone(), x));
+		r.set_at(0, 3, true);
+	}
+
+	proptest! {
+		#[test]
+		fn new_from_relation_vec(v in relation_arbitrary(domain_arbitrary())) {
+			let r = RelationBits::from(v.clone());
+			assert!(relation::eq(&r, &v));
+			assert_eq!(RelationVec::from(r), v);
+		}
+		#[test]
+		fn union(
+			(p, q) in domain_homogeneous().prop_flat_map(
+				|d| (relation_arbitrary(Just(d.clone())), relation_arbitrary(Just(d)))
+			)
+		) {
+			let (r, s) = (RelationBits::from(p.clone()), RelationBits::from(q.clone()));
+			assert!(relation::eq(&r.union_bits(&s), &RelationVec::union(&p, &q)));
+			// the lazy combinators of the Relation trait are not shadowed
+			assert!(relation::eq(&RelationBits::union(&r, &q), &RelationVec::union(&p, &q)));
+		}
+		#[test]
+		fn intersection(
+			(p, q) in domain_homogeneous().prop_flat_map(
+				|d| (relation_arbitrary(Just(d.clone())), relation_arbitrary(Just(d)))
+			)
+		) {
+			let (r, s) = (RelationBits::from(p.clone()), RelationBits::from(q.clone()));
+			assert!(relation::eq(&r.intersection_bits(&s), &RelationVec::intersection(&p, &q)));
+			assert!(relation::eq(&RelationBits::intersection(&r, &q), &RelationVec::intersection(&p, &q)));
+		}
+		#[test]
+		fn complement(v in relation_arbitrary(domain_arbitrary())) {
+			let r = RelationBits::from(v.clone());
+			assert!(relation::eq(&r.complement_bits(), &RelationVec::complement(&v)));
+		}
+		#[test]
+		fn converse(v in relation_arbitrary(domain_arbitrary())) {
+			let r = RelationBits::from(v.clone());
+			assert!(relation::eq(&r.converse_bits(), &RelationVec::converse(&v)));
+		}
+		#[test]
+		fn relation_properties(v in relation_arbitrary(domain_arbitrary())) {
+			relation::relation::tests::relation_property_test(&RelationBits::from(v));
+		}
+		#[test]
+		fn relation_mut((p, q, _) in three_rels()) {
+			relation::relation_mut::tests::relation_mut_test(&RelationBits::from(p), &q);
+		}
+		#[test]
+		fn endorelation_properties(v in relation_arbitrary(domain_homogeneous())) {
+			relation::endorelation::tests::endorelation_property_test(&RelationBits::from(v));
+		}
+	}
+}
@@ -0,0 +1,148 @@
+//! A mutable binary [`Relation`].
+//!
+//! This module contains the `RelationMut` trait, which allows relations
+//! to be edited in place.
+
+use crate::set::SetElement;
+use crate::relation::Relation;
+
+pub trait RelationMut : Relation {
+	/// Set the `Relation`s incidence matrix at column `ix` and row `iy` to `value`.
+	fn set_at(&mut self, ix: usize, iy: usize, value: bool);
+
+	/// Add the pair `(x, y)` to the relation.
+	/// Return `true` if the pair was not related before.
+	fn insert(&mut self, x: &SetElement, y: &SetElement) -> bool {
+		let ix = self.get_domain().0.iter().position(|e| e == x).unwrap();
+		let iy = self.get_domain().1.iter().position(|e| e == y).unwrap();
+		let present = self.eval_at(ix, iy);
+		self.set_at(ix, iy, true);
+		!present
+	}
+	/// Remove the pair `(x, y)` from the relation.
+	/// Return `true` if the pair was related before.
+	fn remove(&mut self, x: &SetElement, y: &SetElement) -> bool {
+		let ix = self.get_domain().0.iter().position(|e| e == x).unwrap();
+		let iy = self.get_domain().1.iter().position(|e| e == y).unwrap();
+		let present = self.eval_at(ix, iy);
+		self.set_at(ix, iy, false);
+		present
+	}
+	/// Negate the `Relation`s incidence matrix at column `ix` and row `iy`.
+	fn toggle(&mut self, ix: usize, iy: usize) {
+		let value = self.eval_at(ix, iy);
+		self.set_at(ix, iy, !value);
+	}
+
+	/// Assign the values of all pairs `(ix, iy)` with the given `ix`,
+	/// i.e. the row `ix` of the incidence matrix.
+	/// Surplus values are ignored.
+	fn set_row<I>(&mut self, ix: usize, values: I)
+	where I: IntoIterator<Item = bool>,
+	{
+		for (iy, value) in self.iys().zip(values) {
+			self.set_at(ix, iy, value);
+		}
+	}
+	/// Assign the values of all pairs `(ix, iy)` with the given `iy`,
+	/// i.e. the column `iy` of the incidence matrix.
+	/// Surplus values are ignored.
+	fn set_column<I>(&mut self, iy: usize, values: I)
+	where I: IntoIterator<Item = bool>,
+	{
+		for (ix, value) in self.ixs().zip(values) {
+			self.set_at(ix, iy, value);
+		}
+	}
+
+	/// Add all pairs of `other` to the relation.
+	///
+	/// See [`Relation::union`] for the lazy variant.
+	fn union_with<R: Relation>(&mut self, other: &R) {
+		debug_assert_eq!(self.get_domain(), other.get_domain());
+		for ix in self.ixs() {
+			for iy in self.iys() {
+				if other.eval_at(ix, iy) {
+					self.set_at(ix, iy, true);
+				}
+			}
+		}
+	}
+	/// Remove all pairs not contained in `other` from the relation.
+	///
+	/// See [`Relation::intersection`] for the lazy variant.
+	fn intersect_with<R: Relation>(&mut self, other: &R) {
+		debug_assert_eq!(self.get_domain(), other.get_domain());
+		for ix in self.ixs() {
+			for iy in self.iys() {
+				if !other.eval_at(ix, iy) {
+					self.set_at(ix, iy, false);
+				}
+			}
+		}
+	}
+	/// Replace the relation by its complement.
+	///
+	/// See [`Relation::complement`] for the lazy variant.
+	fn complement_in_place(&mut self) {
+		for ix in self.ixs() {
+			for iy in self.iys() {
+				self.toggle(ix, iy);
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+pub mod tests {
+	use super::*;
+	use crate::relation::{self, RelationVec};
+
+	pub fn relation_mut_test<R>(r: &R, s: &RelationVec)
+	where R: RelationMut + std::fmt::Debug
+	{
+		debug_assert_eq!(r.get_domain(), s.get_domain());
+
+		// toggle: involutivity
+		let mut t = r.clone();
+		for ix in t.ixs() {
+			for iy in t.iys() {
+				t.toggle(ix, iy);
+			}
+		}
+		assert!(relation::eq(&t, &R::complement(r)));
+		t.complement_in_place();
+		assert!(relation::eq(&t, r));
+
+		// insert, remove
+		let (x, y) = (r.get_domain().0.iter().next(), r.get_domain().1.iter().next());
+		if let (Some(x), Some(y)) = (x, y) {
+			let mut t = r.clone();
+			assert_eq!(t.insert(x, y), !r.eval(x, y));
+			assert!(t.eval(x, y));
+			assert!(!t.insert(x, y));
+			assert!(t.remove(x, y));
+			assert!(!t.eval(x, y));
+			assert!(!t.remove(x, y));
+		}
+
+		// rows and columns
+		let mut t = r.clone();
+		for ix in t.ixs() {
+			t.set_row(ix, std::iter::repeat(true));
+		}
+		assert!(relation::eq(&t, &R::universal(r.get_domain())));
+		for iy in t.iys() {
+			t.set_column(iy, std::iter::repeat(false));
+		}
+		assert!(relation::eq(&t, &R::empty(r.get_domain())));
+
+		// in-place operations agree with their lazy variants
+		let mut t = r.clone();
+		t.union_with(s);
+		assert!(relation::eq(&t, &R::union(r, s)));
+		let mut t = r.clone();
+		t.intersect_with(s);
+		assert!(relation::eq(&t, &R::intersection(r, s)));
+	}
+}
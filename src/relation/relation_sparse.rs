@@ -0,0 +1,274 @@
+//! A binary `Relation`, represented as sorted lists of successors.
+
+use std::vec::Vec;
+use std::fmt;
+
+use crate::set::Set;
+use crate::relation::{Relation, Endorelation, RelationMut, RelationVec};
+
+/// A binary `Relation`, represented as one sorted list of successors per row
+/// of the incidence matrix.
+/// The following example contains the pairs `(0,1), (0,2), (2,0)`:
+///
+/// ```
+/// let rows = vec![
+///     vec![1, 2], // (0,1), (0,2),
+///     vec![],     //
+///     vec![0],    // (2,0),
+/// ];
+/// ```
+///
+/// The lists of predecessors of every column are kept as well, so rows and
+/// columns can both be iterated in time proportional to their length.
+///
+/// Memory usage and most property checks are proportional to the number of
+/// pairs in the relation, which makes this representation suitable for large
+/// relations with only few pairs.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RelationSparse {
+	domain: (Set, Set),
+	rows: Vec<Vec<usize>>,
+	/// The transposed `rows`, i.e. the sorted predecessors of every column.
+	columns: Vec<Vec<usize>>,
+}
+
+impl RelationSparse {
+	/// Create a new [`RelationSparse`] from the given domain and lists of successors.
+	/// The lists are sorted and duplicates are removed.
+	pub fn new(domain: (Set, Set), mut rows: Vec<Vec<usize>>) -> Self {
+		assert!(domain.0.cardinality() == rows.len());
+		for row in rows.iter_mut() {
+			row.sort_unstable();
+			row.dedup();
+			assert!(row.iter().all(|&iy| iy < domain.1.cardinality()));
+		}
+		let columns = transpose(&rows, domain.1.cardinality());
+		RelationSparse {
+			domain,
+			rows,
+			columns,
+		}
+	}
+	/// Create a new [`RelationSparse`] from the given domain and pairs of indices.
+	pub fn from_pairs<I>(domain: (Set, Set), pairs: I) -> Self
+	where I: IntoIterator<Item = (usize, usize)>,
+	{
+		let mut rows = vec![Vec::new(); domain.0.cardinality()];
+		for (ix, iy) in pairs {
+			rows[ix].push(iy);
+		}
+		RelationSparse::new(domain, rows)
+	}
+	/// Create a new [`RelationSparse`] from the given `Relation`.
+	pub fn from_relation<R: Relation>(r: &R) -> Self {
+		let (set1, set2) = r.get_domain();
+		let rows: Vec<Vec<usize>> = r.ixs()
+			.map(|ix| r.iys().filter(|&iy| r.eval_at(ix, iy)).collect())
+			.collect();
+		let columns = transpose(&rows, set2.cardinality());
+
+		RelationSparse {
+			domain: (set1.clone(), set2.clone()),
+			rows,
+			columns,
+		}
+	}
+
+	/// The sorted column-indices `iy` with `(ix, iy)` in the relation.
+	pub fn row(&self, ix: usize) -> &[usize] {
+		&self.rows[ix]
+	}
+	/// The sorted row-indices `ix` with `(ix, iy)` in the relation.
+	pub fn column(&self, iy: usize) -> &[usize] {
+		&self.columns[iy]
+	}
+	/// Iterate all pairs of indices contained in the relation, row by row.
+	pub fn pairs(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+		self.rows.iter().enumerate()
+			.flat_map(|(ix, row)| row.iter().map(move |&iy| (ix, iy)))
+	}
+	/// Number of pairs contained in the relation.
+	pub fn count(&self) -> usize {
+		self.rows.iter().map(Vec::len).sum()
+	}
+}
+
+/// The sorted lists of predecessors of the `n` columns of the sorted `rows`.
+fn transpose(rows: &[Vec<usize>], n: usize) -> Vec<Vec<usize>> {
+	let mut columns = vec![Vec::new(); n];
+	for (ix, row) in rows.iter().enumerate() {
+		for &iy in row.iter() {
+			columns[iy].push(ix);
+		}
+	}
+	columns
+}
+
+impl Relation for RelationSparse {
+	fn get_domain(&self) -> (&Set, &Set) {
+		(&self.domain.0, &self.domain.1)
+	}
+	fn eval_at(&self, ix: usize, iy: usize) -> bool {
+		self.rows[ix].binary_search(&iy).is_ok()
+	}
+
+	fn is_injective(&self) -> bool {
+		self.columns.iter().all(|column| column.len() <= 1)
+	}
+	fn is_functional(&self) -> bool {
+		self.rows.iter().all(|row| row.len() <= 1)
+	}
+	fn is_lefttotal(&self) -> bool {
+		self.rows.iter().all(|row| !row.is_empty())
+	}
+	fn is_surjective(&self) -> bool {
+		self.columns.iter().all(|column| !column.is_empty())
+	}
+	fn source(&self) -> Set {
+		self.domain.0.iter().zip(self.rows.iter())
+			.filter(|(_, row)| !row.is_empty())
+			.map(|(x, _)| x)
+			.cloned()
+			.collect()
+	}
+	fn range(&self) -> Set {
+		self.domain.1.iter().zip(self.columns.iter())
+			.filter(|(_, column)| !column.is_empty())
+			.map(|(y, _)| y)
+			.cloned()
+			.collect()
+	}
+}
+
+impl Endorelation for RelationSparse {}
+
+impl RelationMut for RelationSparse {
+	fn set_at(&mut self, ix: usize, iy: usize, value: bool) {
+		assert!(iy < self.domain.1.cardinality());
+		let (row, column) = (&mut self.rows[ix], &mut self.columns[iy]);
+		match (row.binary_search(&iy), value) {
+			(Err(i), true) => {
+				row.insert(i, iy);
+				let j = column.binary_search(&ix).unwrap_err();
+				column.insert(j, ix);
+			}
+			(Ok(i), false) => {
+				row.remove(i);
+				let j = column.binary_search(&ix).unwrap();
+				column.remove(j);
+			}
+			_ => {}
+		}
+	}
+}
+
+impl From<RelationVec> for RelationSparse {
+	fn from(r: RelationVec) -> Self {
+		RelationSparse::from_relation(&r)
+	}
+}
+
+impl From<RelationSparse> for RelationVec {
+	fn from(r: RelationSparse) -> Self {
+		let mut table = vec![false; r.domain.0.cardinality() * r.domain.1.cardinality()];
+		for (ix, iy) in r.pairs() {
+			table[ix * r.domain.1.cardinality() + iy] = true;
+		}
+		RelationVec::new(r.domain, table)
+	}
+}
+
+impl fmt::Display for RelationSparse {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.domain.0)?;
+		write!(f, " x ")?;
+		write!(f, "{}", self.domain.1)?;
+		writeln!(f, " :")?;
+		for row in self.rows.iter() {
+			write!(f, "|")?;
+			let mut successors = row.iter().peekable();
+			for iy in self.iys() {
+				let related = successors.next_if_eq(&&iy).is_some();
+				write!(f, "{}", if related { 1 } else { 0 })?;
+			}
+			writeln!(f, "|")?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::relation;
+	use crate::relation::relation_vec::tests::{
+		domain_arbitrary, domain_homogeneous, relation_arbitrary, three_rels,
+	};
+	use proptest::prelude::*;
+
+	#[test]
+	fn new_normalizes_rows() {
+		let x: Set = (0..3).collect();
+		let y: Set = ['a', 'b', 'c'].iter().collect();
+		let r = RelationSparse::new((x, y), vec![vec![2, 0, 2], vec![], vec![1]]);
+		assert_eq!(r.row(0), &[0, 2]);
+		assert_eq!(r.count(), 3);
+		assert_eq!(r.column(2), &[0]);
+		assert_eq!(r.pairs().collect::<Vec<_>>(), vec![(0, 0), (0, 2), (2, 1)]);
+	}
+	#[test]
+	fn from_pairs_large() {
+		let n: Set = (0..10_000).collect();
+		let r = RelationSparse::from_pairs((n.clone(), n), (0..9_999).map(|i| (i, i + 1)));
+		assert_eq!(r.count(), 9_999);
+		assert!(r.is_functional());
+		assert!(r.is_injective());
+		assert!(!r.is_lefttotal());
+		assert!(!r.is_surjective());
+		assert_eq!(r.source().cardinality(), 9_999);
+		assert_eq!(r.range().cardinality(), 9_999);
+	}
+
+	proptest! {
+		#[test]
+		fn new_from_relation_vec(v in relation_arbitrary(domain_arbitrary())) {
+			let r = RelationSparse::from(v.clone());
+			assert!(relation::eq(&r, &v));
+			assert_eq!(r.to_string(), v.to_string());
+			assert_eq!(RelationVec::from(r), v);
+		}
+		#[test]
+		fn column((p, q, _) in three_rels()) {
+			// the columns stay consistent with the rows while editing
+			let mut r = RelationSparse::from(p);
+			for (ix, iy) in q.ixs().flat_map(|ix| q.iys().map(move |iy| (ix, iy))) {
+				if q.eval_at(ix, iy) {
+					r.toggle(ix, iy);
+				}
+			}
+			for iy in r.iys() {
+				let expected: Vec<usize> = r.ixs().filter(|&ix| r.eval_at(ix, iy)).collect();
+				assert_eq!(r.column(iy), &expected[..]);
+			}
+		}
+		#[test]
+		fn relation_properties(v in relation_arbitrary(domain_arbitrary())) {
+			let r = RelationSparse::from(v.clone());
+			relation::relation::tests::relation_property_test(&r);
+			assert_eq!(r.is_injective(), v.is_injective());
+			assert_eq!(r.is_functional(), v.is_functional());
+			assert_eq!(r.is_lefttotal(), v.is_lefttotal());
+			assert_eq!(r.is_surjective(), v.is_surjective());
+			assert_eq!(r.source(), v.source());
+			assert_eq!(r.range(), v.range());
+		}
+		#[test]
+		fn relation_mut((p, q, _) in three_rels()) {
+			relation::relation_mut::tests::relation_mut_test(&RelationSparse::from(p), &q);
+		}
+		#[test]
+		fn endorelation_properties(v in relation_arbitrary(domain_homogeneous())) {
+			relation::endorelation::tests::endorelation_property_test(&RelationSparse::from(v));
+		}
+	}
+}
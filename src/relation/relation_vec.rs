@@ -5,7 +5,7 @@ use std::fmt;
 use std::string::ToString;
 
 use crate::set::Set;
-use crate::relation::{Relation, Endorelation};
+use crate::relation::{Relation, Endorelation, RelationMut};
 
 /// A binary `Relation`, represented as a `std::vec::Vec` of `bool`ean values.
 /// Values are storen row-wise, as shown in the following example, to form an
@@ -81,6 +81,19 @@ impl Relation for RelationVec {
 
 impl Endorelation for RelationVec {}
 
+impl RelationMut for RelationVec {
+	fn set_at(&mut self, ix: usize, iy: usize, value: bool) {
+		assert!(iy < self.domain.1.cardinality());
+		let i = self.get_table_index(ix, iy);
+		self.table[i] = value;
+	}
+	fn complement_in_place(&mut self) {
+		for b in self.table.iter_mut() {
+			*b = !*b;
+		}
+	}
+}
+
 impl fmt::Display for RelationVec {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		write!(f, "{}", self.domain.0)?;
@@ -99,13 +112,13 @@ impl fmt::Display for RelationVec {
 }
 
 #[cfg(test)]
-mod tests {
+pub mod tests {
 	use super::*;
-	use crate::set::Set;
+	use crate::set::{Set, SetElement};
 	use crate::relation;
 	use proptest::{prelude::*, collection::vec};
 
-	type Domain = (Set, Set);
+	pub type Domain = (Set, Set);
 
 	const ALPHABET: [char; 26] = [
 		'a','b','c','d','e','f','g','h','i',
@@ -144,6 +157,14 @@ mod tests {
 		assert!(!r.eval_at(1, 0)); assert!( r.eval_at(1, 1));
 		assert!(!r.eval_at(2, 0)); assert!(!r.eval_at(2, 1));
 	}
+	#[test]
+	#[should_panic]
+	fn set_at_out_of_row() {
+		// the column must not spill into the next row
+		let x: Set = (0..3).collect();
+		let mut r = RelationVec::new((x.clone(), x), vec![false; 9]);
+		r.set_at(0, 3, true);
+	}
 
 	mod endorelation {
 		use super::*;
@@ -276,12 +297,149 @@ mod tests {
 			];
 			let r1 = RelationVec::new(domain, table);
 			assert!(r1.is_difunctional());
+			let rectangles = r1.difunctional_decomposition().unwrap();
+			let set = |v: Vec<i32>| v.into_iter().collect::<Set>();
+			assert_eq!(rectangles, vec![
+				(set(vec![0, 3]), set(vec![2, 5, 7, 8, 9])),
+				(set(vec![1, 2, 8]), set(vec![3, 4])),
+				(set(vec![5, 6]), set(vec![0])),
+				(set(vec![7]), set(vec![1])),
+			]);
+		}
+		#[test]
+		fn difunctional_heterogeneous() {
+			let x: Set = (0..4).collect();
+			let y: Set = ['a', 'b', 'c'].iter().collect();
+			let r = RelationVec::new((x, y), vec![
+				true,  false, false,
+				false, true,  false,
+				true,  false, false,
+				false, false, false,
+			]);
+			assert!(r.is_difunctional());
+			assert_eq!(r.difunctional_decomposition().unwrap().len(), 2);
+			// 0 and 2 share a, 2 and 3 now share c
+			let s = RelationVec::new(r.domain.clone(), vec![
+				true,  false, false,
+				false, true,  false,
+				true,  false, true,
+				false, false, true,
+			]);
+			assert!(!s.is_difunctional());
+			assert!(s.difunctional_decomposition().is_none());
+			let c = RelationVec::closure_difunctional(&s);
+			let expected = RelationVec::new(r.domain.clone(), vec![
+				true,  false, true,
+				false, true,  false,
+				true,  false, true,
+				true,  false, true,
+			]);
+			assert!(relation::eq(&c, &expected));
 		}
 
-		/*
-		fn is_lattice() {}
-		fn is_sublattice() {}
+		#[test]
+		fn is_ferrers() {
+			// interval order of the intervals [0,1], [2,3], [1,3], [4,5]: x completely before y
+			let domain: Domain = ((0..4).collect(), (0..4).collect());
+			let table = vec![
+				false, true,  false, true,
+				false, false, false, true,
+				false, false, false, true,
+				false, false, false, false,
+			];
+			let r1 = RelationVec::new(domain.clone(), table);
+			assert!(r1.is_ferrers());
+			assert!(relation::eq(&RelationVec::upper_ferrers_hull(&r1), &r1));
+			let (rows, columns) = r1.staircase();
+			assert_eq!(rows, vec![0, 1, 2, 3]);
+			assert_eq!(columns, vec![3, 1, 0, 2]);
+
+			let table = vec![
+				false, true,  false, false,
+				false, false, false, true,
+				false, false, false, false,
+				false, false, false, false,
+			];
+			let r2 = RelationVec::new(domain, table);
+			assert!(!r2.is_ferrers());
+			let c = RelationVec::upper_ferrers_hull(&r2);
+			assert!(c.is_ferrers());
+			assert!(c.eval_at(0, 3));
+			assert!(c.eval_at(1, 1));
+		}
+		#[test]
+		fn is_ferrers_heterogeneous() {
+			let x: Set = (0..3).collect();
+			let y: Set = ['a', 'b'].iter().collect();
+			let r = RelationVec::new((x, y), vec![
+				true,  false,
+				true,  true,
+				false, false,
+			]);
+			assert!(r.is_ferrers());
+			assert!(!RelationVec::identity((r.get_domain().0, r.get_domain().0)).is_ferrers());
+		}
+
+		#[test]
+		fn is_lattice() {
+			let divisors_of_60 = [1, 2, 3, 4, 5, 6, 10, 12, 15, 20, 30, 60];
+			let r1 = RelationVec::from_predicate(&divisors_of_60, |(&x, &y)| y % x == 0);
+			assert!(r1.is_lattice());
+			let n32: Vec<u8> = (1..=32).collect();
+			let r2 = RelationVec::from_predicate(&n32, |(&x, &y)| y % x == 0);
+			assert!(!r2.is_lattice());
+		}
+
+		#[test]
+		fn transitive_reduction() {
+			let n70: Vec<u8> = (0..70).collect();
+			let lt = RelationVec::from_predicate(&n70, |(x, y)| x < y);
+			let succ = RelationVec::from_predicate(&n70, |(&x, &y)| x + 1 == y);
+			assert!(relation::eq(&RelationVec::transitive_reduction(&lt).unwrap(), &succ));
+			// loops are kept
+			let le = RelationVec::from_predicate(&n70, |(x, y)| x <= y);
+			let reduction = RelationVec::transitive_reduction(&le).unwrap();
+			assert!(relation::eq(&reduction, &RelationVec::closure_reflexive(&succ)));
+			// cycles have no unique reduction
+			let cycle = RelationVec::from_predicate(&[0, 1, 2], |(&x, &y)| (x + 1) % 3 == y);
+			assert!(RelationVec::transitive_reduction(&cycle).is_none());
+		}
+		#[test]
+		fn hasse_diagram() {
+			let r = RelationVec::from_predicate(&[1, 2, 3, 4, 6], |(&x, &y)| y % x == 0);
+			let pairs: Vec<(SetElement, SetElement)> = [(1, 2), (1, 3), (2, 4), (2, 6), (3, 6)].iter()
+				.map(|&(x, y)| (SetElement::from(x), SetElement::from(y)))
+				.collect();
+			assert_eq!(r.covering_pairs(), Some(pairs));
+			assert_eq!(RelationVec::hasse_diagram(&r).unwrap().count(), 5);
+			let lt = RelationVec::from_predicate(&[1, 2, 3, 4, 6], |(x, y)| x < y);
+			assert!(RelationVec::hasse_diagram(&lt).is_none());
+			assert!(lt.covering_pairs().is_none());
+		}
+		#[test]
+		fn bounds() {
+			let r = RelationVec::from_predicate(&[1, 2, 3, 4, 6], |(&x, &y)| y % x == 0);
+			let set = |s: &[i32]| s.iter().map(|&i| SetElement::from(i)).collect::<Set>();
+			let (u, v, w) = (set(&[2, 3]), set(&[4, 6]), set(&[2, 4, 6]));
+			assert_eq!(r.minimal_elements(&u), u);
+			assert_eq!(r.maximal_elements(&u), u);
+			assert_eq!(r.least_element(&u), None);
+			assert_eq!(r.upper_bounds(&u), set(&[6]));
+			assert_eq!(r.lower_bounds(&u), set(&[1]));
+			assert_eq!(r.supremum(&u), Some(SetElement::from(6)));
+			assert_eq!(r.infimum(&u), Some(SetElement::from(1)));
+			assert_eq!(r.upper_bounds(&v), Set::new());
+			assert_eq!(r.supremum(&v), None);
+			assert_eq!(r.infimum(&v), Some(SetElement::from(2)));
+			assert_eq!(r.least_element(&w), Some(SetElement::from(2)));
+			assert_eq!(r.greatest_element(&w), None);
+			assert_eq!(r.maximal_elements(&w), v);
+			// the empty set is bounded by every element
+			assert_eq!(r.supremum(&Set::new()), Some(SetElement::from(1)));
+			assert_eq!(r.infimum(&Set::new()), None);
+		}
 
+		/*
 		fn is_injective() {}
 		fn is_functional() {}
 		fn is_lefttotal() {}
@@ -290,6 +448,111 @@ mod tests {
 		fn is_function() {}
 		*/
 
+		#[test]
+		fn residuals() {
+			// for a partial order E: E \ E = E / E = E and syq(E, E) = I
+			let e = RelationVec::from_predicate(&[1, 2, 3, 4, 6], |(&x, &y)| y % x == 0);
+			assert!(relation::eq(&RelationVec::left_residual(&e, &e), &e));
+			assert!(relation::eq(&RelationVec::right_residual(&e, &e), &e));
+			let id = RelationVec::identity(e.get_domain());
+			assert!(relation::eq(&RelationVec::symmetric_quotient(&e, &e), &id));
+		}
+		#[test]
+		fn kronecker() {
+			// the synchronous product of two cycles of coprime lengths is a cycle
+			let c2 = RelationVec::from_predicate(&[0, 1], |(&x, &y)| (x + 1) % 2 == y);
+			let c3 = RelationVec::from_predicate(&[0, 1, 2], |(&x, &y)| (x + 1) % 3 == y);
+			let c6 = RelationVec::kronecker(&c2, &c3);
+			assert_eq!(c6.get_domain().0.cardinality(), 6);
+			assert!(c6.is_function() && c6.is_bijective());
+			let reachable = RelationVec::closure_transitive(&c6);
+			assert!(relation::eq(&reachable, &RelationVec::universal(c6.get_domain())));
+			// but not of cycles of the same length
+			let c4 = RelationVec::kronecker(&c2, &c2);
+			let reachable = RelationVec::closure_transitive(&c4);
+			assert!(!relation::eq(&reachable, &RelationVec::universal(c4.get_domain())));
+		}
+		#[test]
+		fn induced_subrelation() {
+			// the divisors of 6 within the divisibility order on 1..9
+			let d = RelationVec::from_predicate(&[1, 2, 3, 4, 5, 6, 7, 8, 9], |(&x, &y)| y % x == 0);
+			let divisors: Set = [1, 2, 3, 6, 12].iter().cloned().collect();
+			let sub = RelationVec::induced_subrelation(&d, &divisors);
+			assert_eq!(sub.get_domain().0.cardinality(), 4);
+			assert!(sub.is_partial_order() && sub.is_boolean());
+			assert!(sub.eval(&SetElement::from(2), &SetElement::from(6)));
+			assert!(!sub.eval(&SetElement::from(2), &SetElement::from(3)));
+			let row = RelationVec::restrict_source(&d, &std::iter::once(2).collect());
+			assert_eq!(row.image(row.get_domain().0).cardinality(), 4);
+		}
+		#[test]
+		fn subrelation() {
+			use std::cmp::Ordering;
+			let lt = RelationVec::from_predicate(&[1, 2, 3], |(x, y)| x < y);
+			let le = RelationVec::from_predicate(&[1, 2, 3, 4], |(x, y)| x <= y);
+			let gt = RelationVec::from_predicate(&[1, 2, 3, 4], |(x, y)| x > y);
+			// the domains differ, but the pairs are compared by their elements
+			assert!(!relation::eq(&lt, &le));
+			assert!(relation::is_subrelation(&lt, &le));
+			assert!(relation::is_strict_subrelation(&lt, &le));
+			assert!(!relation::is_subrelation(&le, &lt));
+			assert!(relation::is_disjoint(&lt, &gt));
+			assert!(!relation::is_disjoint(&le, &le));
+			assert_eq!(relation::compare(&lt, &le), Some(Ordering::Less));
+			assert_eq!(relation::compare(&le, &lt), Some(Ordering::Greater));
+			assert_eq!(relation::compare(&le, &gt), None);
+			let lt4 = RelationVec::from_predicate(&[1, 2, 3, 4], |(x, y)| x < y);
+			let lt3 = RelationVec::induced_subrelation(&lt4, lt.get_domain().0);
+			assert_eq!(relation::compare(&lt, &lt3), Some(Ordering::Equal));
+			assert!(relation::eq(&lt, &lt3));
+		}
+		#[test]
+		fn closure_transitive() {
+			// successor relation on 0..70
+			let n70: Vec<u8> = (0..70).collect();
+			let r = RelationVec::from_predicate(&n70, |(&x, &y)| x + 1 == y);
+			let t = RelationVec::closure_transitive(&r);
+			let lt = RelationVec::from_predicate(&n70, |(x, y)| x < y);
+			assert!(relation::eq(&t, &lt));
+			let rt = RelationVec::closure_reflexive_transitive(&r);
+			let le = RelationVec::from_predicate(&n70, |(x, y)| x <= y);
+			assert!(relation::eq(&rt, &le));
+		}
+		#[test]
+		fn closure_transitive_combinators() {
+			let n8: Vec<u8> = (0..8).collect();
+			let p = RelationVec::from_predicate(&n8, |(&x, &y)| x + 2 == y);
+			let q = RelationVec::from_predicate(&n8, |(&x, &y)| x == 7 && y == 0);
+			// 0 → 2 → 4 → 6, 1 → 3 → 5 → 7 → 0
+			let t = RelationVec::closure_transitive(&RelationVec::union(&p, &q));
+			assert!(t.eval_at(1, 6));
+			assert!(!t.eval_at(0, 1));
+			assert!(!t.eval_at(6, 6));
+			// 0 → 4 → 0
+			let pp = RelationVec::concatenation(&p, &p);
+			let qq = RelationVec::from_predicate(&n8, |(&x, &y)| x == 4 && y == 0);
+			let t = RelationVec::closure_transitive(&RelationVec::union(&pp, &qq));
+			assert!(t.eval_at(0, 0));
+			assert!(t.eval_at(4, 4));
+			assert!(!t.eval_at(2, 2));
+		}
+
+		#[test]
+		fn equivalence_classes() {
+			let n9: Vec<u8> = (0..9).collect();
+			// merge aliases x ~ x + 3
+			let r = RelationVec::from_predicate(&n9, |(&x, &y)| x + 3 == y);
+			let classes = RelationVec::equivalence_classes(&r);
+			assert_eq!(classes, vec![
+				vec![0, 3, 6].into_iter().collect::<Set>(),
+				vec![1, 4, 7].into_iter().collect::<Set>(),
+				vec![2, 5, 8].into_iter().collect::<Set>(),
+			]);
+			let e = RelationVec::closure_equivalence(&r);
+			let mod3 = RelationVec::from_predicate(&n9, |(&x, &y)| x % 3 == y % 3);
+			assert!(relation::eq(&e, &mod3));
+		}
+
 		#[test]
 		fn relation_mod8_equal() {
 			let n32: Vec<u8> = (1..=32).collect();
@@ -325,7 +588,7 @@ mod tests {
 
 	/// Generates domains ({1,2, ..., n}, {1,2, ..., n})
 	/// with n: [2;32[
-	fn domain_homogeneous() -> impl Strategy<Value = Domain> {
+	pub fn domain_homogeneous() -> impl Strategy<Value = Domain> {
 		// draw a random number n from [2;32[
 		(2..32)
 			// generate a sequence [1;n]
@@ -338,7 +601,7 @@ mod tests {
 
 	/// Generates domains ({1,2, ..., n}, {'a, 'b', ..., ch})
 	/// with n: [2;32[ and ch: ['b';'z']
-	fn domain_heterogeneous() -> impl Strategy<Value = Domain> {
+	pub fn domain_heterogeneous() -> impl Strategy<Value = Domain> {
 		// draw random numbers n, c from [2;32[, [2;26[
 		(2..32usize, 2..26usize)
 			// generate sequences [1;n], [0;c]
@@ -358,26 +621,26 @@ mod tests {
 			)
 	}
 
-	fn domain_arbitrary() -> impl Strategy<Value = Domain> {
+	pub fn domain_arbitrary() -> impl Strategy<Value = Domain> {
 		prop_oneof![
 			domain_homogeneous(),
 			domain_heterogeneous(),
 		]
 	}
 
-	fn relation_for_domain(domain: Domain) -> impl Strategy<Value = RelationVec> {
+	pub fn relation_for_domain(domain: Domain) -> impl Strategy<Value = RelationVec> {
 		let dimension = domain.0.cardinality() * domain.1.cardinality();
 		let domains = Just(domain);
 		let tables = vec(any::<bool>(), dimension);
 		(domains, tables).prop_map(|(d, t)| RelationVec::new(d, t))
 	}
 
-	fn relation_arbitrary(domain: impl Strategy<Value = Domain>) -> impl Strategy<Value = RelationVec> {
+	pub fn relation_arbitrary(domain: impl Strategy<Value = Domain>) -> impl Strategy<Value = RelationVec> {
 		domain.prop_flat_map(relation_for_domain)
 	}
 
 	prop_compose! {
-		fn three_rels() (d in domain_homogeneous()) (
+		pub fn three_rels() (d in domain_homogeneous()) (
 			r in relation_for_domain(d.clone()),
 			s in relation_for_domain(d.clone()),
 			t in relation_for_domain(d),
@@ -386,6 +649,18 @@ mod tests {
 		}
 	}
 
+	prop_compose! {
+		/// Three relations over {1, 2, ..., n} with n: [1;6[, small enough
+		/// for products of their domains.
+		pub fn three_small_rels() (n in 1..6) (
+			r in relation_for_domain(((1..n+1).collect(), (1..n+1).collect())),
+			s in relation_for_domain(((1..n+1).collect(), (1..n+1).collect())),
+			t in relation_for_domain(((1..n+1).collect(), (1..n+1).collect())),
+		) -> (RelationVec, RelationVec, RelationVec) {
+			(r, s, t)
+		}
+	}
+
 	proptest! {
 		#[test]
 		fn new_from_relation(r in relation_arbitrary(domain_arbitrary())) {
@@ -408,6 +683,14 @@ mod tests {
 			relation::relation::tests::distributivity_concatenation(&r, &s, &t);
 		}
 		#[test]
+		fn relation_difunctional(r in relation_arbitrary(domain_arbitrary())) {
+			relation::relation::tests::difunctional(&r);
+		}
+		#[test]
+		fn relation_ferrers(r in relation_arbitrary(domain_arbitrary())) {
+			relation::relation::tests::ferrers(&r);
+		}
+		#[test]
 		fn relation_converse(r in relation_arbitrary(domain_arbitrary())) {
 			relation::relation::tests::converse(&r);
 		}
@@ -432,9 +715,29 @@ mod tests {
 			relation::relation::tests::distributivity_intersection(&r, &s, &t);
 		}
 		#[test]
+		fn relation_residuals((r, s, t) in three_rels()) {
+			relation::relation::tests::residuals(&r, &s, &t);
+		}
+		#[test]
+		fn relation_kronecker((r, s, t) in three_small_rels()) {
+			relation::relation::tests::kronecker(&r, &s, &t);
+		}
+		#[test]
+		fn relation_restriction((r, s, t) in three_rels()) {
+			relation::relation::tests::restriction(&r, &s.source(), &t.range());
+		}
+		#[test]
+		fn relation_inclusion((r, s, _) in three_rels()) {
+			relation::relation::tests::inclusion(&r, &s);
+		}
+		#[test]
 		fn relation_de_morgan((r, s, _) in three_rels()) {
 			relation::relation::tests::de_morgan(&r, &s);
 		}
+		#[test]
+		fn relation_mut((r, s, _) in three_rels()) {
+			relation::relation_mut::tests::relation_mut_test(&r, &s);
+		}
 	}
 
 	proptest! {
@@ -442,5 +745,17 @@ mod tests {
 		fn endorelation_properties(r in relation_arbitrary(domain_homogeneous())) {
 			relation::endorelation::tests::endorelation_property_test(&r);
 		}
+		#[test]
+		fn endorelation_closures(r in relation_arbitrary(domain_homogeneous())) {
+			relation::endorelation::tests::closures(&r);
+		}
+		#[test]
+		fn endorelation_reductions(r in relation_arbitrary(domain_homogeneous())) {
+			relation::endorelation::tests::reductions(&r);
+		}
+		#[test]
+		fn endorelation_bounds(r in relation_arbitrary(domain_homogeneous())) {
+			relation::endorelation::tests::bounds(&r);
+		}
 	}
 }
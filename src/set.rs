@@ -3,17 +3,25 @@ use std::fmt;
 use std::iter;
 use std::string::ToString;
 
+/// The largest set whose power set is built, see [`Set::powerset`].
+pub const MAX_POWERSET: usize = 20;
+
 /// Implementation of a [`Set`].
 /// Slightly rudimentary at the moment.
 /* HashSet does not implement Hash, so it cannot be nested (currently) */
 #[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Set(BTreeSet<SetElement>);
 
-/// A [`SetElement`] is either a [`std::string::String`] or a nested [`Set`].
+/// A [`SetElement`] is either a [`std::string::String`], a nested [`Set`]
+/// or a tuple of elements.
+///
+/// Tuples are ordered lexicographically, so the pairs of
+/// [`Set::cartesian_product`] are sorted by their first component.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum SetElement {
 	Str(String),
 	Set(Set),
+	Tuple(Vec<SetElement>),
 }
 
 impl Set {
@@ -26,6 +34,9 @@ impl Set {
 	pub fn iter(&self) -> ::std::collections::btree_set::Iter<SetElement> {
 		self.0.iter()
 	}
+	pub fn contains(&self, e: &SetElement) -> bool {
+		self.0.contains(e)
+	}
 	pub fn is_subset(&self, other: &Set) -> bool {
 		self.0.is_subset(&other.0)
 	}
@@ -41,6 +52,30 @@ impl Set {
 			u: other.0.iter().enumerate().peekable(),
 		}
 	}
+	/// The cartesian product `X × Y` of all pairs `(x, y)`.
+	pub fn cartesian_product(&self, other: &Set) -> Set {
+		self.iter()
+			.flat_map(|x| other.iter().map(move |y| SetElement::from((x.clone(), y.clone()))))
+			.collect()
+	}
+	/// The power set `𝒫(X)` of all subsets, as nested sets.
+	///
+	/// The `2ⁿ` subsets hold `n·2ⁿ⁻¹` elements in total, so this panics if
+	/// the set has more than [`MAX_POWERSET`] elements.
+	pub fn powerset(&self) -> Set {
+		let elements: Vec<&SetElement> = self.iter().collect();
+		assert!(elements.len() <= MAX_POWERSET, "power set too large");
+		(0..1u64 << elements.len()).map(|mask| subset(&elements, mask)).collect()
+	}
+}
+
+/// The subset of the `elements` selected by the bits of `mask`.
+pub(crate) fn subset(elements: &[&SetElement], mask: u64) -> Set {
+	elements.iter()
+		.enumerate()
+		.filter(|&(i, _)| mask >> i & 1 == 1)
+		.map(|(_, &e)| e.clone())
+		.collect()
 }
 
 #[derive(Clone, Debug)]
@@ -102,6 +137,17 @@ impl fmt::Display for SetElement {
 		match self {
 			SetElement::Str(s) => write!(f, "{}", s),
 			SetElement::Set(s) => write!(f, "{}", s),
+			SetElement::Tuple(t) => {
+				write!(f, "(")?;
+				let mut it = t.iter();
+				if let Some(e) = it.next() {
+					write!(f, "{}", e)?;
+					for e in it {
+						write!(f, ", {}", e)?;
+					}
+				}
+				write!(f, ")")
+			}
 		}
 	}
 }
@@ -114,6 +160,18 @@ impl From<String> for SetElement {
 	}
 }
 
+impl From<Set> for SetElement {
+	fn from(s: Set) -> SetElement {
+		SetElement::Set(s)
+	}
+}
+
+impl From<(SetElement, SetElement)> for SetElement {
+	fn from((x, y): (SetElement, SetElement)) -> SetElement {
+		SetElement::Tuple(vec![x, y])
+	}
+}
+
 impl From<i32> for SetElement {
 	fn from(i: i32) -> SetElement {
 		SetElement::Str(i.to_string())
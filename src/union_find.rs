@@ -0,0 +1,53 @@
+/// A disjoint-set forest over the indices `0..n`.
+/// Uses union by size and path halving.
+#[derive(Clone, Debug)]
+pub struct UnionFind {
+	parent: Vec<usize>,
+	size: Vec<usize>,
+}
+
+impl UnionFind {
+	/// Create `n` singleton sets `{0}, {1}, ..., {n-1}`.
+	pub fn new(n: usize) -> Self {
+		UnionFind {
+			parent: (0..n).collect(),
+			size: vec![1; n],
+		}
+	}
+	/// Return the representative of the set containing `i`.
+	pub fn find(&mut self, mut i: usize) -> usize {
+		while self.parent[i] != i {
+			self.parent[i] = self.parent[self.parent[i]];
+			i = self.parent[i];
+		}
+		i
+	}
+	/// Merge the sets containing `i` and `j`.
+	/// Return `false` if they already were the same set.
+	pub fn union(&mut self, i: usize, j: usize) -> bool {
+		let (mut i, mut j) = (self.find(i), self.find(j));
+		if i == j { return false; }
+		if self.size[i] < self.size[j] {
+			std::mem::swap(&mut i, &mut j);
+		}
+		self.parent[j] = i;
+		self.size[i] += self.size[j];
+		true
+	}
+	/// Return all sets, each sorted and ordered by their smallest element.
+	pub fn sets(&mut self) -> Vec<Vec<usize>> {
+		let mut index: Vec<Option<usize>> = vec![None; self.parent.len()];
+		let mut sets: Vec<Vec<usize>> = Vec::new();
+		for i in 0..self.parent.len() {
+			let root = self.find(i);
+			match index[root] {
+				Some(k) => sets[k].push(i),
+				None => {
+					index[root] = Some(sets.len());
+					sets.push(vec![i]);
+				}
+			}
+		}
+		sets
+	}
+}